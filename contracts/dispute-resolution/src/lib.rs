@@ -79,6 +79,17 @@ impl DisputeContract {
             escrow_split_id,
         };
 
+        // Put the escrow on hold so it cannot be released or cancelled while voting runs;
+        // only `resolve_dispute` can move it out again. The escrow rejects raisers who are
+        // neither its creator nor an obligated participant.
+        let hold_sym = Symbol::new(&env, "hold_for_dispute");
+        let hold_args: soroban_sdk::Vec<Val> = vec![
+            &env,
+            dispute.raiser.into_val(&env),
+            dispute.escrow_split_id.into_val(&env),
+        ];
+        env.invoke_contract::<()>(&dispute.escrow_contract, &hold_sym, hold_args);
+
         storage::save_dispute(&env, &dispute);
         storage::add_to_list(&env, dispute_id.clone());
 
@@ -155,32 +166,61 @@ impl DisputeContract {
             DisputeResult::Tied
         };
 
-        // Auth boundary: only the escrow creator (owner) is allowed to finalize the escrow action.
+        // Auth boundary: only the escrow creator or a co-manager allowed to release the split
+        // may finalize the escrow action.
         resolver.require_auth();
 
-        let get_creator_sym = Symbol::new(&env, "get_creator");
-        let get_creator_args: soroban_sdk::Vec<Val> =
-            vec![&env, dispute.escrow_split_id.into_val(&env)];
-        let escrow_creator: Address =
-            env.invoke_contract(&dispute.escrow_contract, &get_creator_sym, get_creator_args);
-
-        if resolver != escrow_creator {
+        // Encoded like the escrow's `Permission::Release` unit variant.
+        let release_permission = vec![&env, Symbol::new(&env, "Release")];
+        let has_permission_sym = Symbol::new(&env, "has_permission");
+        let has_permission_args: soroban_sdk::Vec<Val> = vec![
+            &env,
+            dispute.escrow_split_id.into_val(&env),
+            resolver.into_val(&env),
+            release_permission.into_val(&env),
+        ];
+        let may_resolve: bool = env.invoke_contract(
+            &dispute.escrow_contract,
+            &has_permission_sym,
+            has_permission_args,
+        );
+
+        if !may_resolve {
             return Err(Error::UnauthorizedResolver);
         }
 
         // Drive the next step in the payment lifecycle by updating escrow settlement state.
         // Upheld => dispute is valid => cancel/undo the escrow.
-        // Dismissed/Tied => dispute is invalid or tie => continue settlement by releasing funds.
+        // Dismissed/Tied => dispute is invalid or tie => lift the hold so the split returns to
+        // its pre-dispute status, and release it if it was ready to be released.
         if result == DisputeResult::UpheldForRaiser {
             let reverse_sym = Symbol::new(&env, "reverse_split");
-            let reverse_args: soroban_sdk::Vec<Val> =
-                vec![&env, dispute.escrow_split_id.into_val(&env)];
+            let reverse_args: soroban_sdk::Vec<Val> = vec![
+                &env,
+                resolver.into_val(&env),
+                dispute.escrow_split_id.into_val(&env),
+            ];
             env.invoke_contract::<()>(&dispute.escrow_contract, &reverse_sym, reverse_args);
         } else {
-            let release_sym = Symbol::new(&env, "release_funds");
-            let release_args: soroban_sdk::Vec<Val> =
+            let lift_sym = Symbol::new(&env, "lift_dispute_hold");
+            let lift_args: soroban_sdk::Vec<Val> =
                 vec![&env, dispute.escrow_split_id.into_val(&env)];
-            env.invoke_contract::<()>(&dispute.escrow_contract, &release_sym, release_args);
+            // Encoded like the escrow's `SplitStatus` unit variants.
+            let restored: soroban_sdk::Vec<Symbol> =
+                env.invoke_contract(&dispute.escrow_contract, &lift_sym, lift_args);
+            let releasable = [
+                vec![&env, Symbol::new(&env, "Ready")],
+                vec![&env, Symbol::new(&env, "PartiallyReleased")],
+            ];
+            if releasable.contains(&restored) {
+                let release_sym = Symbol::new(&env, "release_funds");
+                let release_args: soroban_sdk::Vec<Val> = vec![
+                    &env,
+                    resolver.into_val(&env),
+                    dispute.escrow_split_id.into_val(&env),
+                ];
+                env.invoke_contract::<()>(&dispute.escrow_contract, &release_sym, release_args);
+            }
         }
 
         dispute.status = DisputeStatus::Resolved;
@@ -5,10 +5,11 @@ use crate::{DisputeContract, DisputeContractClient};
 use soroban_sdk::token::{Client as TokenClient, StellarAssetClient as TokenAdminClient};
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
-    Address, Env, Map, String,
+    Address, Env, Map, String, Vec,
 };
 use split_escrow::{
-    SplitEscrowContract, SplitEscrowContractClient, SplitStatus as EscrowSplitStatus,
+    CreateEscrowParams, Permission, SplitEscrowContract, SplitEscrowContractClient,
+    SplitStatus as EscrowSplitStatus, YieldPolicy,
 };
 
 fn setup() -> (
@@ -42,18 +43,26 @@ fn setup() -> (
     escrow_client.initialize(&admin, &token_address, &String::from_str(&env, "1.0.0"));
 
     let treasury = Address::generate(&env);
-    escrow_client.set_treasury(&treasury);
-    escrow_client.set_fee(&0u32);
-
-    let escrow_split_id = escrow_client.create_escrow(
-        &creator,
-        &String::from_str(&env, "Escrow split for disputes"),
-        &10_000i128,
-        &Map::new(&env),
-        &None,
-        &false,
-        &None,
-    );
+    escrow_client.set_treasury(&admin, &treasury);
+    escrow_client.set_fee(&admin, &0u32);
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 10_000i128);
+    let escrow_split_id = escrow_client.create_escrow(&CreateEscrowParams {
+        creator: creator.clone(),
+        description: String::from_str(&env, "Escrow split for disputes"),
+        total_amount: 10_000i128,
+        token: token_address.clone(),
+        obligations,
+        payouts: Vec::new(&env),
+        milestones: Vec::new(&env),
+        max_participants: None,
+        whitelist_enabled: false,
+        metadata: Map::new(&env),
+        note: None,
+        deadline: None,
+        yield_policy: YieldPolicy::Disabled,
+    });
     token_admin_client.mint(&participant, &10_000i128);
     escrow_client.deposit(&escrow_split_id, &participant, &10_000i128);
 
@@ -65,6 +74,7 @@ fn setup() -> (
 
     let dispute_id = env.register_contract(None, DisputeContract);
     let dispute_client = DisputeContractClient::new(&env, &dispute_id);
+    escrow_client.set_dispute_contract(&dispute_id);
 
     (
         env,
@@ -85,18 +95,18 @@ fn test_raise_dispute_records_voting_state() {
     let (
         env,
         client,
-        _escrow,
+        escrow,
         escrow_contract,
         _token_client,
         _creator,
-        _participant,
+        participant,
         _treasury,
         escrow_split_id,
         _admin,
     ) = setup();
     env.ledger().with_mut(|l| l.timestamp = 1000);
 
-    let raiser = Address::generate(&env);
+    let raiser = participant.clone();
     let id = client.raise_dispute(
         &String::from_str(&env, "split_001"),
         &raiser,
@@ -110,6 +120,11 @@ fn test_raise_dispute_records_voting_state() {
     assert_eq!(dispute.votes_for, 0);
     assert_eq!(dispute.votes_against, 0);
     assert_eq!(dispute.voting_ends_at, 1000 + 604_800);
+    // The escrow is on hold until the dispute is resolved.
+    assert_eq!(
+        escrow.get_escrow(&escrow_split_id).status,
+        EscrowSplitStatus::Disputed
+    );
 }
 
 #[test]
@@ -121,14 +136,14 @@ fn test_vote_on_dispute_increments_counts() {
         escrow_contract,
         _token_client,
         _creator,
-        _participant,
+        participant,
         _treasury,
         escrow_split_id,
         _admin,
     ) = setup();
     env.ledger().with_mut(|l| l.timestamp = 1000);
 
-    let raiser = Address::generate(&env);
+    let raiser = participant.clone();
     let voter = Address::generate(&env);
 
     let id = client.raise_dispute(
@@ -155,14 +170,14 @@ fn test_vote_against_increments_votes_against() {
         escrow_contract,
         _token_client,
         _creator,
-        _participant,
+        participant,
         _treasury,
         escrow_split_id,
         _admin,
     ) = setup();
     env.ledger().with_mut(|l| l.timestamp = 1000);
 
-    let raiser = Address::generate(&env);
+    let raiser = participant.clone();
     let voter = Address::generate(&env);
 
     let id = client.raise_dispute(
@@ -189,14 +204,14 @@ fn test_double_vote_returns_error() {
         escrow_contract,
         _token_client,
         _creator,
-        _participant,
+        participant,
         _treasury,
         escrow_split_id,
         _admin,
     ) = setup();
     env.ledger().with_mut(|l| l.timestamp = 1000);
 
-    let raiser = Address::generate(&env);
+    let raiser = participant.clone();
     let voter = Address::generate(&env);
 
     let id = client.raise_dispute(
@@ -229,7 +244,7 @@ fn test_upheld_dispute_cancels_and_refunds_escrow() {
     ) = setup();
     env.ledger().with_mut(|l| l.timestamp = 1000);
 
-    let raiser = Address::generate(&env);
+    let raiser = participant.clone();
     let voter1 = Address::generate(&env);
     let voter2 = Address::generate(&env);
 
@@ -274,7 +289,7 @@ fn test_dismissed_dispute_releases_escrow() {
     ) = setup();
     env.ledger().with_mut(|l| l.timestamp = 1000);
 
-    let raiser = Address::generate(&env);
+    let raiser = participant.clone();
     let voter1 = Address::generate(&env);
     let voter2 = Address::generate(&env);
 
@@ -318,7 +333,7 @@ fn test_tied_dispute_releases_escrow() {
     ) = setup();
     env.ledger().with_mut(|l| l.timestamp = 1000);
 
-    let raiser = Address::generate(&env);
+    let raiser = participant.clone();
     let voter1 = Address::generate(&env);
     let voter2 = Address::generate(&env);
 
@@ -355,14 +370,14 @@ fn test_resolve_before_voting_window_returns_error() {
         escrow_contract,
         _token_client,
         creator,
-        _participant,
+        participant,
         _treasury,
         escrow_split_id,
         _admin,
     ) = setup();
     env.ledger().with_mut(|l| l.timestamp = 1000);
 
-    let raiser = Address::generate(&env);
+    let raiser = participant.clone();
     let id = client.raise_dispute(
         &String::from_str(&env, "split_008"),
         &raiser,
@@ -385,14 +400,14 @@ fn test_vote_after_period_returns_error() {
         escrow_contract,
         _token_client,
         _creator,
-        _participant,
+        participant,
         _treasury,
         escrow_split_id,
         _admin,
     ) = setup();
     env.ledger().with_mut(|l| l.timestamp = 1000);
 
-    let raiser = Address::generate(&env);
+    let raiser = participant.clone();
     let voter = Address::generate(&env);
 
     let id = client.raise_dispute(
@@ -417,14 +432,14 @@ fn test_only_escrow_creator_can_resolve() {
         escrow_contract,
         _token_client,
         _creator,
-        _participant,
+        participant,
         _treasury,
         escrow_split_id,
         _admin,
     ) = setup();
     env.ledger().with_mut(|l| l.timestamp = 1000);
 
-    let raiser = Address::generate(&env);
+    let raiser = participant.clone();
     let voter = Address::generate(&env);
     let not_creator = Address::generate(&env);
 
@@ -450,3 +465,140 @@ fn test_only_escrow_creator_can_resolve() {
     // Sanity: expected error variant should be preserved in host result.
     let _ = Error::UnauthorizedResolver;
 }
+
+#[test]
+fn test_co_manager_with_release_permission_can_resolve() {
+    let (
+        env,
+        client,
+        escrow_client,
+        escrow_contract,
+        token_client,
+        creator,
+        participant,
+        _treasury,
+        escrow_split_id,
+        _admin,
+    ) = setup();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let raiser = participant.clone();
+    let voter = Address::generate(&env);
+    let canceller = Address::generate(&env);
+    let releaser = Address::generate(&env);
+    escrow_client.set_manager(
+        &escrow_split_id,
+        &canceller,
+        &Vec::from_array(&env, [Permission::Cancel]),
+    );
+    escrow_client.set_manager(
+        &escrow_split_id,
+        &releaser,
+        &Vec::from_array(&env, [Permission::Release]),
+    );
+
+    let id = client.raise_dispute(
+        &String::from_str(&env, "split_011"),
+        &raiser,
+        &String::from_str(&env, "Resolved by a co-manager"),
+        &escrow_contract,
+        &escrow_split_id,
+    );
+    client.vote_on_dispute(&id, &voter, &true);
+    env.ledger().with_mut(|l| l.timestamp = 1000 + 604_801);
+
+    let res = client.try_resolve_dispute(&id, &canceller);
+    assert_eq!(res, Err(Ok(Error::UnauthorizedResolver)));
+
+    let result = client.resolve_dispute(&id, &releaser);
+    assert_eq!(result, DisputeResult::UpheldForRaiser);
+    assert_eq!(
+        escrow_client.get_escrow(&escrow_split_id).status,
+        EscrowSplitStatus::Cancelled
+    );
+    assert_eq!(token_client.balance(&creator), 0i128);
+}
+
+#[test]
+fn test_outsider_cannot_put_escrow_on_hold() {
+    let (
+        env,
+        client,
+        escrow_client,
+        escrow_contract,
+        _token_client,
+        _creator,
+        _participant,
+        _treasury,
+        escrow_split_id,
+        _admin,
+    ) = setup();
+
+    let outsider = Address::generate(&env);
+    let res = client.try_raise_dispute(
+        &String::from_str(&env, "split_012"),
+        &outsider,
+        &String::from_str(&env, "Not my split"),
+        &escrow_contract,
+        &escrow_split_id,
+    );
+    assert!(res.is_err());
+    assert_eq!(
+        escrow_client.get_escrow(&escrow_split_id).status,
+        EscrowSplitStatus::Ready
+    );
+}
+
+#[test]
+fn test_dismissed_dispute_on_unfunded_escrow_leaves_it_pending() {
+    let (
+        env,
+        client,
+        escrow_client,
+        escrow_contract,
+        token_client,
+        creator,
+        participant,
+        _treasury,
+        _escrow_split_id,
+        _admin,
+    ) = setup();
+    env.ledger().with_mut(|l| l.timestamp = 1000);
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 5_000i128);
+    let pending_split_id = escrow_client.create_escrow(&CreateEscrowParams {
+        creator: creator.clone(),
+        description: String::from_str(&env, "Still collecting"),
+        total_amount: 5_000i128,
+        token: token_client.address.clone(),
+        obligations,
+        payouts: Vec::new(&env),
+        milestones: Vec::new(&env),
+        max_participants: None,
+        whitelist_enabled: false,
+        metadata: Map::new(&env),
+        note: None,
+        deadline: None,
+        yield_policy: YieldPolicy::Disabled,
+    });
+
+    let id = client.raise_dispute(
+        &String::from_str(&env, "split_013"),
+        &participant,
+        &String::from_str(&env, "Wrong amount"),
+        &escrow_contract,
+        &pending_split_id,
+    );
+    client.vote_on_dispute(&id, &Address::generate(&env), &false);
+    env.ledger().with_mut(|l| l.timestamp = 1000 + 604_801);
+
+    let result = client.resolve_dispute(&id, &creator);
+    assert_eq!(result, DisputeResult::DismissedForRaiser);
+    // Nothing to release yet, so the split just goes back to collecting deposits.
+    assert_eq!(
+        escrow_client.get_escrow(&pending_split_id).status,
+        EscrowSplitStatus::Pending
+    );
+    assert_eq!(token_client.balance(&creator), 0i128);
+}
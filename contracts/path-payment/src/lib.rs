@@ -172,6 +172,34 @@ impl PathPaymentContract {
         Self::execute_path_payment_internal(env, caller, split_id, path, amount_in, max_slippage)
     }
 
+    /// Same as `execute_path_payment`, then forwards the received dest_asset to `recipient`.
+    /// Used by contracts (e.g. split-escrow) that need the converted funds in hand.
+    pub fn execute_path_payment_to(
+        env: Env,
+        caller: Address,
+        recipient: Address,
+        split_id: String,
+        path: Vec<Asset>,
+        amount_in: i128,
+        max_slippage: u32,
+    ) -> Result<i128, Error> {
+        let received = Self::execute_path_payment_internal(
+            env.clone(),
+            caller,
+            split_id,
+            path.clone(),
+            amount_in,
+            max_slippage,
+        )?;
+        let dest = path.get(path.len() - 1).unwrap();
+        token::Client::new(&env, dest.address()).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &received,
+        );
+        Ok(received)
+    }
+
     fn execute_path_payment_internal(
         env: Env,
         caller: Address,
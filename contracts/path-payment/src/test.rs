@@ -248,6 +248,27 @@ fn test_execute_path_payment_single_asset() {
     assert_eq!(token_client.balance(&contract_id), amount);
 }
 
+#[test]
+fn test_execute_path_payment_to_forwards_output() {
+    let (env, admin, token_a, _token_b, contract_id, client, token_client, stellar_token) =
+        setup_with_tokens();
+    client.initialize(&admin);
+    let caller = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    stellar_token.mint(&caller, &500_0000000i128);
+    env.mock_all_auths();
+    let mut path = Vec::new(&env);
+    path.push_back(Asset(token_a.clone()));
+    let split_id = String::from_str(&env, "split-1");
+    let amount = 100_0000000i128;
+    let received =
+        client.execute_path_payment_to(&caller, &recipient, &split_id, &path, &amount, &0u32);
+    assert_eq!(received, amount);
+    assert_eq!(token_client.balance(&caller), 400_0000000i128);
+    assert_eq!(token_client.balance(&recipient), amount);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
 #[test]
 fn test_execute_path_payment_invalid_amount() {
     let (env, admin, token_a, _token_b, _contract_id, client, _, _) = setup_with_tokens();
@@ -0,0 +1,101 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{errors::Error, events, storage, types::Role};
+
+/// Require the current admin's authorization.
+pub fn require_admin(env: &Env) -> Result<Address, Error> {
+    if !storage::has_admin(env) {
+        return Err(Error::NotInitialized);
+    }
+    let admin = storage::get_admin(env);
+    admin.require_auth();
+    Ok(admin)
+}
+
+/// Require `caller`'s authorization and that it is the admin or holds `role`.
+pub fn require_role(env: &Env, caller: &Address, role: Role) -> Result<(), Error> {
+    if !storage::has_admin(env) {
+        return Err(Error::NotInitialized);
+    }
+    caller.require_auth();
+    if *caller == storage::get_admin(env) || storage::has_role(env, &role, caller) {
+        return Ok(());
+    }
+    Err(Error::Unauthorized)
+}
+
+pub fn grant_role(env: &Env, role: Role, account: &Address) -> Result<(), Error> {
+    let admin = require_admin(env)?;
+    storage::set_role(env, &role, account, true);
+    events::emit_role_granted(env, &role, account, &admin);
+    Ok(())
+}
+
+pub fn revoke_role(env: &Env, role: Role, account: &Address) -> Result<(), Error> {
+    let admin = require_admin(env)?;
+    storage::set_role(env, &role, account, false);
+    events::emit_role_revoked(env, &role, account, &admin);
+    Ok(())
+}
+
+/// First step of an admin transfer: the current admin nominates `new_admin`.
+/// Proposing again replaces the pending nominee.
+pub fn propose_admin(env: &Env, new_admin: &Address) -> Result<(), Error> {
+    let admin = require_admin(env)?;
+    storage::set_pending_admin(env, new_admin);
+    events::emit_admin_proposed(env, &admin, new_admin);
+    Ok(())
+}
+
+/// Second step: the nominee accepts and becomes admin.
+pub fn accept_admin(env: &Env) -> Result<(), Error> {
+    let pending = storage::get_pending_admin(env).ok_or(Error::NoPendingAdmin)?;
+    pending.require_auth();
+    let previous = storage::get_admin(env);
+    storage::set_admin(env, &pending);
+    storage::clear_pending_admin(env);
+    events::emit_admin_transferred(env, &previous, &pending);
+    Ok(())
+}
+
+/// Fails with `Paused` while the global circuit breaker is engaged.
+pub fn require_not_paused(env: &Env) -> Result<(), Error> {
+    if storage::is_paused(env) {
+        return Err(Error::Paused);
+    }
+    Ok(())
+}
+
+/// Fails with `Paused` while the contract is paused or `split_id` is frozen.
+pub fn require_split_open(env: &Env, split_id: u64) -> Result<(), Error> {
+    require_not_paused(env)?;
+    if storage::is_frozen(env, split_id) {
+        return Err(Error::Paused);
+    }
+    Ok(())
+}
+
+pub fn set_paused(env: &Env, caller: &Address, paused: bool) -> Result<(), Error> {
+    require_role(env, caller, Role::Pauser)?;
+    storage::set_paused(env, paused);
+    events::emit_paused(env, paused, caller);
+    Ok(())
+}
+
+pub fn set_frozen(env: &Env, caller: &Address, split_id: u64, frozen: bool) -> Result<(), Error> {
+    require_role(env, caller, Role::Pauser)?;
+    if storage::get_split(env, split_id).is_none() {
+        return Err(Error::SplitNotFound);
+    }
+    storage::set_frozen(env, split_id, frozen);
+    events::emit_split_frozen(env, split_id, frozen, caller);
+    Ok(())
+}
+
+/// Require the registered dispute contract's authorization. A contract calling
+/// in directly is authorized implicitly.
+pub fn require_dispute_contract(env: &Env) -> Result<(), Error> {
+    let contract = storage::get_dispute_contract(env).ok_or(Error::Unauthorized)?;
+    contract.require_auth();
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use soroban_sdk::{token, Address, Env, Map, Vec};
+
+use crate::{
+    errors::Error,
+    events, payouts, staking, state_machine, storage,
+    types::{Amendment, Split, SplitStatus},
+};
+
+/// Store a proposal to replace `split_id`'s obligations and total. Proposing
+/// again discards any earlier proposal and its approvals.
+pub fn propose(
+    env: &Env,
+    split_id: u64,
+    obligations: Map<Address, i128>,
+    total_amount: i128,
+) -> Result<(), Error> {
+    let split = storage::get_split(env, split_id).ok_or(Error::SplitNotFound)?;
+    split.creator.require_auth();
+    if split.status != SplitStatus::Pending {
+        return Err(Error::SplitNotPending);
+    }
+    // Applying may refund deposits, which must be held by the escrow.
+    if staking::locked(env, split_id) > 0 {
+        return Err(Error::FundsStaked);
+    }
+
+    crate::validate_obligations(&obligations, total_amount)?;
+    payouts::validate(&split.payouts, total_amount)?;
+    crate::validate_milestones(&split.milestones, total_amount)?;
+
+    let affected = affected_participants(env, &split.obligations, &obligations);
+    if affected.is_empty() {
+        return Err(Error::InvalidInput);
+    }
+
+    storage::set_amendment(
+        env,
+        split_id,
+        &Amendment {
+            obligations,
+            total_amount,
+            affected,
+            approvals: Vec::new(env),
+        },
+    );
+    events::emit_amendment_proposed(env, split_id, total_amount);
+    Ok(())
+}
+
+/// Record `participant`'s consent. The last required approval applies the
+/// amendment; returns whether that happened.
+pub fn approve(env: &Env, split_id: u64, participant: &Address) -> Result<bool, Error> {
+    participant.require_auth();
+    let mut amendment = storage::get_amendment(env, split_id).ok_or(Error::AmendmentNotFound)?;
+    if !amendment.affected.contains(participant) {
+        return Err(Error::Unauthorized);
+    }
+    if !amendment.approvals.contains(participant) {
+        amendment.approvals.push_back(participant.clone());
+        events::emit_amendment_approved(env, split_id, participant);
+    }
+
+    if amendment.approvals.len() < amendment.affected.len() {
+        storage::set_amendment(env, split_id, &amendment);
+        return Ok(false);
+    }
+
+    let mut split = storage::get_split(env, split_id).ok_or(Error::SplitNotFound)?;
+    if split.status != SplitStatus::Pending {
+        return Err(Error::SplitNotPending);
+    }
+    if staking::locked(env, split_id) > 0 {
+        return Err(Error::FundsStaked);
+    }
+    apply(env, &mut split, amendment)?;
+    storage::remove_amendment(env, split_id);
+    Ok(true)
+}
+
+/// Participants whose obligation differs between `current` and `proposed`.
+fn affected_participants(
+    env: &Env,
+    current: &Map<Address, i128>,
+    proposed: &Map<Address, i128>,
+) -> Vec<Address> {
+    let mut affected = Vec::new(env);
+    for (participant, amount) in current.iter() {
+        if proposed.get(participant.clone()) != Some(amount) {
+            affected.push_back(participant);
+        }
+    }
+    for (participant, _) in proposed.iter() {
+        if !current.contains_key(participant.clone()) {
+            affected.push_back(participant);
+        }
+    }
+    affected
+}
+
+/// Swap in the new obligations and refund any balance above a participant's new
+/// obligation. A fully funded split moves to `Ready`.
+fn apply(env: &Env, split: &mut Split, amendment: Amendment) -> Result<(), Error> {
+    let token_client = token::Client::new(env, &split.token);
+    let contract = env.current_contract_address();
+
+    let mut refunded = 0i128;
+    for (participant, balance) in split.balances.clone().iter() {
+        let obligation = amendment.obligations.get(participant.clone()).unwrap_or(0);
+        if balance <= obligation {
+            continue;
+        }
+        let excess = balance - obligation;
+        token_client.transfer(&contract, &participant, &excess);
+        if obligation == 0 {
+            split.balances.remove(participant.clone());
+            crate::remove_participant(&mut split.participants, &participant);
+        } else {
+            split.balances.set(participant.clone(), obligation);
+        }
+        refunded += excess;
+    }
+    split.deposited_amount -= refunded;
+
+    for participant in split.obligations.keys().iter() {
+        if !amendment.obligations.contains_key(participant.clone()) {
+            storage::unindex_by_participant(env, &participant, split.split_id);
+        }
+    }
+    for participant in amendment.obligations.keys().iter() {
+        storage::index_by_participant(env, &participant, split.split_id);
+    }
+
+    split.obligations = amendment.obligations;
+    split.total_amount = amendment.total_amount;
+    if split.deposited_amount == split.total_amount {
+        state_machine::apply(env, split, SplitStatus::Ready)?;
+    }
+    storage::set_split(env, split);
+    events::emit_amendment_applied(env, split.split_id, split.total_amount, refunded);
+    Ok(())
+}
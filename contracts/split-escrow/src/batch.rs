@@ -0,0 +1,130 @@
+use soroban_sdk::{token, Address, Env, Vec};
+
+use crate::{
+    access,
+    errors::Error,
+    events, managers, staking, state_machine, storage,
+    types::{BatchOutcome, Permission, Split},
+};
+
+/// Largest batch accepted by `deposit_many` and `release_many`.
+pub const MAX_BATCH_SIZE: u32 = 20;
+
+fn check_size(len: u32) -> Result<(), Error> {
+    if len == 0 || len > MAX_BATCH_SIZE {
+        return Err(Error::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Load `split_id` for a batch item, rejecting repeats within the same batch so every
+/// item can be validated against stored state independently.
+fn load_item(env: &Env, split_id: u64, seen: &mut Vec<u64>) -> Result<Split, Error> {
+    if seen.contains(split_id) {
+        return Err(Error::InvalidInput);
+    }
+    seen.push_back(split_id);
+    access::require_split_open(env, split_id)?;
+    storage::get_split(env, split_id).ok_or(Error::SplitNotFound)
+}
+
+/// Turn first-pass results into outcomes when at least one item failed: failing
+/// items carry their error code, the rest are marked as not applied.
+fn rejected(env: &Env, errors: &Vec<Option<u32>>) -> Option<Vec<BatchOutcome>> {
+    let mut any_failed = false;
+    let mut outcomes = Vec::new(env);
+    for error in errors.iter() {
+        match error {
+            Some(code) => {
+                any_failed = true;
+                outcomes.push_back(BatchOutcome::Rejected(code));
+            }
+            None => outcomes.push_back(BatchOutcome::NotApplied),
+        }
+    }
+    if any_failed {
+        Some(outcomes)
+    } else {
+        None
+    }
+}
+
+/// Validate every `(split_id, amount)` item, then either credit all of them or
+/// none. Returns `Applied(amount)` per item on success.
+pub fn deposit_many(
+    env: &Env,
+    participant: &Address,
+    items: &Vec<(u64, i128)>,
+) -> Result<Vec<BatchOutcome>, Error> {
+    participant.require_auth();
+    check_size(items.len())?;
+
+    let mut seen = Vec::new(env);
+    let mut errors = Vec::new(env);
+    for (split_id, amount) in items.iter() {
+        let result = load_item(env, split_id, &mut seen)
+            .and_then(|split| crate::check_deposit(env, &split, participant, amount));
+        errors.push_back(result.err().map(|e| e as u32));
+    }
+    if let Some(outcomes) = rejected(env, &errors) {
+        return Ok(outcomes);
+    }
+
+    let contract = env.current_contract_address();
+    let mut outcomes = Vec::new(env);
+    for (split_id, amount) in items.iter() {
+        let mut split = storage::get_split(env, split_id).ok_or(Error::SplitNotFound)?;
+        crate::credit_deposit(env, &mut split, participant, amount)?;
+        token::Client::new(env, &split.token).transfer(participant, &contract, &amount);
+        storage::set_split(env, &split);
+        events::emit_deposit(env, split_id, participant, amount);
+        outcomes.push_back(BatchOutcome::Applied(amount));
+    }
+    Ok(outcomes)
+}
+
+/// Validate every split, then either release all of them or none. `caller` must be
+/// each split's creator or a co-manager with `Release`. Returns `Applied(net_amount)`
+/// per item on success.
+pub fn release_many(
+    env: &Env,
+    caller: &Address,
+    split_ids: &Vec<u64>,
+) -> Result<Vec<BatchOutcome>, Error> {
+    caller.require_auth();
+    check_size(split_ids.len())?;
+
+    let mut seen = Vec::new(env);
+    let mut errors = Vec::new(env);
+    for split_id in split_ids.iter() {
+        let result = load_item(env, split_id, &mut seen).and_then(|split| {
+            if !managers::has_permission(env, &split, caller, Permission::Release) {
+                return Err(Error::Unauthorized);
+            }
+            if !state_machine::can_release(&split.status) {
+                return Err(Error::SplitNotReady);
+            }
+            if staking::locked(env, split.split_id) > 0 {
+                return Err(Error::FundsStaked);
+            }
+            if storage::get_treasury(env).is_none() {
+                return Err(Error::TreasuryNotSet);
+            }
+            Ok(())
+        });
+        errors.push_back(result.err().map(|e| e as u32));
+    }
+    if let Some(outcomes) = rejected(env, &errors) {
+        return Ok(outcomes);
+    }
+
+    let mut outcomes = Vec::new(env);
+    for split_id in split_ids.iter() {
+        let mut split = storage::get_split(env, split_id).ok_or(Error::SplitNotFound)?;
+        let released_amount = crate::settle(env, &mut split)?;
+        storage::set_split(env, &split);
+        events::emit_released(env, split_id, released_amount);
+        outcomes.push_back(BatchOutcome::Applied(released_amount));
+    }
+    Ok(outcomes)
+}
@@ -0,0 +1,98 @@
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    token, vec, Address, Env, IntoVal, InvokeError, String, Symbol, Vec,
+};
+
+use crate::{errors::Error, storage, types::PathAsset};
+
+/// Widest path the path-payment contract accepts, source and destination included.
+const MAX_PATH_LEN: u32 = 6;
+
+/// `[source, ..hops, dest]` in the path-payment contract's representation.
+fn build_path(
+    env: &Env,
+    source_asset: &Address,
+    hops: &Vec<Address>,
+    dest: &Address,
+) -> Result<Vec<PathAsset>, Error> {
+    let mut path = vec![env, PathAsset(source_asset.clone())];
+    for hop in hops.iter() {
+        path.push_back(PathAsset(hop));
+    }
+    path.push_back(PathAsset(dest.clone()));
+    if path.len() > MAX_PATH_LEN {
+        return Err(Error::InvalidInput);
+    }
+    Ok(path)
+}
+
+/// Decimal form of `split_id`, used as the path-payment contract's split label.
+fn split_label(env: &Env, split_id: u64) -> String {
+    let mut digits = [0u8; 20];
+    let mut start = digits.len();
+    let mut rest = split_id;
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (rest % 10) as u8;
+        rest /= 10;
+        if rest == 0 {
+            break;
+        }
+    }
+    String::from_bytes(env, &digits[start..])
+}
+
+/// Convert `source_amount` of `source_asset`, already held by this contract, into a
+/// different asset `dest` through the registered path-payment contract. Returns the amount of `dest` received.
+pub fn convert(
+    env: &Env,
+    split_id: u64,
+    source_asset: &Address,
+    source_amount: i128,
+    hops: &Vec<Address>,
+    dest: &Address,
+    max_slippage_bps: u32,
+) -> Result<i128, Error> {
+    let path_payment =
+        storage::get_path_payment_contract(env).ok_or(Error::ConversionUnavailable)?;
+    let path = build_path(env, source_asset, hops, dest)?;
+    let this = env.current_contract_address();
+
+    // The path-payment contract pulls the source amount from us with a plain transfer.
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: source_asset.clone(),
+                fn_name: Symbol::new(env, "transfer"),
+                args: (this.clone(), path_payment.clone(), source_amount).into_val(env),
+            },
+            sub_invocations: vec![env],
+        }),
+    ]);
+
+    let dest_client = token::Client::new(env, dest);
+    let before = dest_client.balance(&this);
+    let args = (
+        this.clone(),
+        this.clone(),
+        split_label(env, split_id),
+        path,
+        source_amount,
+        max_slippage_bps,
+    )
+        .into_val(env);
+    let result = env.try_invoke_contract::<i128, InvokeError>(
+        &path_payment,
+        &Symbol::new(env, "execute_path_payment_to"),
+        args,
+    );
+    if !matches!(result, Ok(Ok(_))) {
+        return Err(Error::ConversionFailed);
+    }
+    let received = dest_client.balance(&this) - before;
+    if received <= 0 {
+        return Err(Error::ConversionFailed);
+    }
+    Ok(received)
+}
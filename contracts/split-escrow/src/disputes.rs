@@ -0,0 +1,61 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    access,
+    errors::Error,
+    state_machine, storage,
+    types::{DisputeHold, SplitStatus},
+};
+
+/// How long a dispute hold lasts before the admin may lift it, in case the dispute is
+/// never resolved. Well beyond the dispute contract's seven-day voting period.
+pub const HOLD_TIMEOUT: u64 = 30 * 24 * 60 * 60;
+
+/// Move `split_id` to `Disputed` on behalf of `raiser`, who must be its creator or hold an
+/// obligation in it, remembering the status to return to if the dispute is dismissed.
+pub fn hold(env: &Env, split_id: u64, raiser: &Address) -> Result<(), Error> {
+    access::require_dispute_contract(env)?;
+    let mut split = storage::get_split(env, split_id).ok_or(Error::SplitNotFound)?;
+    if *raiser != split.creator && !split.obligations.contains_key(raiser.clone()) {
+        return Err(Error::Unauthorized);
+    }
+    let previous = split.status.clone();
+    state_machine::apply(env, &mut split, SplitStatus::Disputed)?;
+    storage::set_dispute_hold(
+        env,
+        split_id,
+        &DisputeHold {
+            previous,
+            since: env.ledger().timestamp(),
+        },
+    );
+    storage::set_split(env, &split);
+    Ok(())
+}
+
+/// Dispute contract only: return a `Disputed` split to the status it had before the
+/// hold and report that status.
+pub fn lift(env: &Env, split_id: u64) -> Result<SplitStatus, Error> {
+    access::require_dispute_contract(env)?;
+    restore(env, split_id)
+}
+
+/// Admin only: return a split whose dispute was never resolved to its pre-dispute status
+/// once the hold is `HOLD_TIMEOUT` old.
+pub fn clear_stale(env: &Env, split_id: u64) -> Result<SplitStatus, Error> {
+    access::require_admin(env)?;
+    let hold = storage::get_dispute_hold(env, split_id).ok_or(Error::SplitNotActive)?;
+    if env.ledger().timestamp() < hold.since.saturating_add(HOLD_TIMEOUT) {
+        return Err(Error::DeadlineNotReached);
+    }
+    restore(env, split_id)
+}
+
+fn restore(env: &Env, split_id: u64) -> Result<SplitStatus, Error> {
+    let mut split = storage::get_split(env, split_id).ok_or(Error::SplitNotFound)?;
+    let hold = storage::get_dispute_hold(env, split_id).ok_or(Error::SplitNotActive)?;
+    state_machine::apply(env, &mut split, hold.previous.clone())?;
+    storage::remove_dispute_hold(env, split_id);
+    storage::set_split(env, &split);
+    Ok(hold.previous)
+}
@@ -24,4 +24,30 @@ pub enum Error {
     ParticipantNotOwed = 16,
     InsufficientFulfillment = 17,
     TotalAmountMismatch = 18,
+    DeadlinePassed = 19,
+    DeadlineNotReached = 20,
+    InsufficientBalance = 21,
+    TokenNotAllowed = 22,
+    InvalidPayouts = 23,
+    InvalidMilestones = 24,
+    MilestoneOutOfOrder = 25,
+    InvalidFeeSchedule = 26,
+    MigrationPending = 27,
+    NoPendingAdmin = 28,
+    Paused = 29,
+    RecurringNotFound = 30,
+    CycleNotDue = 31,
+    CyclesExhausted = 32,
+    InsufficientAllowance = 33,
+    AmendmentNotFound = 34,
+    IntentExpired = 35,
+    InvalidNonce = 36,
+    ConversionUnavailable = 37,
+    ConversionFailed = 38,
+    StakingUnavailable = 39,
+    YieldNotEnabled = 40,
+    FundsStaked = 41,
+    CooldownActive = 42,
+    NoPendingCreator = 43,
+    AmendmentPending = 44,
 }
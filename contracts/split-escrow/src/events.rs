@@ -1,11 +1,85 @@
-use soroban_sdk::{Address, Env, String, Symbol};
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
-use crate::types::Split;
+use crate::types::{
+    AppliedFee, FeeSchedule, Limits, Permission, RecurringSplit, Role, Split, SplitStatus,
+    StakingConfig, TtlPolicy, YieldPolicy,
+};
 
 pub fn emit_initialized(env: &Env, admin: &Address) {
     env.events().publish(("init", "admin"), admin.clone());
 }
 
+pub fn emit_admin_proposed(env: &Env, admin: &Address, proposed: &Address) {
+    env.events().publish(
+        (Symbol::new(env, "AdminProposed"), admin.clone()),
+        proposed.clone(),
+    );
+}
+
+pub fn emit_admin_transferred(env: &Env, previous: &Address, new_admin: &Address) {
+    env.events().publish(
+        (Symbol::new(env, "AdminTransferred"), previous.clone()),
+        new_admin.clone(),
+    );
+}
+
+pub fn emit_role_granted(env: &Env, role: &Role, account: &Address, by: &Address) {
+    env.events().publish(
+        (Symbol::new(env, "RoleGranted"), *role, account.clone()),
+        by.clone(),
+    );
+}
+
+pub fn emit_role_revoked(env: &Env, role: &Role, account: &Address, by: &Address) {
+    env.events().publish(
+        (Symbol::new(env, "RoleRevoked"), *role, account.clone()),
+        by.clone(),
+    );
+}
+
+pub fn emit_paused(env: &Env, paused: bool, by: &Address) {
+    env.events()
+        .publish((Symbol::new(env, "Paused"), by.clone()), paused);
+}
+
+pub fn emit_split_frozen(env: &Env, split_id: u64, frozen: bool, by: &Address) {
+    env.events().publish(
+        (Symbol::new(env, "SplitFrozen"), split_id, by.clone()),
+        frozen,
+    );
+}
+
+pub fn emit_dispute_contract_updated(env: &Env, contract: &Address) {
+    env.events().publish(
+        (Symbol::new(env, "DisputeContractUpdated"),),
+        contract.clone(),
+    );
+}
+
+pub fn emit_path_payment_contract_updated(env: &Env, contract: &Address) {
+    env.events().publish(
+        (Symbol::new(env, "PathPaymentContractUpdated"),),
+        contract.clone(),
+    );
+}
+
+pub fn emit_staking_config_updated(env: &Env, config: &StakingConfig) {
+    env.events().publish(
+        (Symbol::new(env, "StakingContractUpdated"),),
+        config.clone(),
+    );
+}
+
+pub fn emit_limits_updated(env: &Env, limits: &Limits) {
+    env.events()
+        .publish((Symbol::new(env, "LimitsUpdated"),), limits.clone());
+}
+
+pub fn emit_ttl_policy_updated(env: &Env, policy: &TtlPolicy) {
+    env.events()
+        .publish((Symbol::new(env, "TtlPolicyUpdated"),), policy.clone());
+}
+
 pub fn emit_contract_upgraded(env: &Env, old_version: String, new_version: String) {
     env.events().publish(
         ("upgraded", "old_version", "new_version"),
@@ -13,6 +87,25 @@ pub fn emit_contract_upgraded(env: &Env, old_version: String, new_version: Strin
     );
 }
 
+pub fn emit_token_allowlist_updated(env: &Env, token: &Address, allowed: bool) {
+    env.events()
+        .publish(("token_allowlist", "token"), (token.clone(), allowed));
+}
+
+pub fn emit_status_changed(env: &Env, split_id: u64, from: &SplitStatus, to: &SplitStatus) {
+    env.events().publish(
+        (Symbol::new(env, "StatusChanged"), split_id),
+        (split_id, from.clone(), to.clone()),
+    );
+}
+
+pub fn emit_migrated(env: &Env, from_version: u32, cursor: u64, migrated: u32) {
+    env.events().publish(
+        ("migrated", "from_version"),
+        (from_version, cursor, migrated),
+    );
+}
+
 pub fn emit_split_created(env: &Env, split: &Split) {
     env.events().publish(
         ("created", "split_id", "creator"),
@@ -20,6 +113,109 @@ pub fn emit_split_created(env: &Env, split: &Split) {
     );
 }
 
+pub fn emit_recurring_created(env: &Env, recurring: &RecurringSplit) {
+    env.events().publish(
+        ("recurring", "recurring_id", "creator"),
+        (
+            recurring.recurring_id,
+            recurring.creator.clone(),
+            recurring.period,
+            recurring.cycles,
+        ),
+    );
+}
+
+pub fn emit_cycle_rolled(
+    env: &Env,
+    recurring_id: u64,
+    cycle: u32,
+    split_id: u64,
+    previous: Option<u64>,
+) {
+    env.events().publish(
+        ("cycle", "recurring_id", "split_id"),
+        (recurring_id, cycle, split_id, previous),
+    );
+}
+
+pub fn emit_recurring_paused(env: &Env, recurring_id: u64, paused: bool) {
+    env.events()
+        .publish(("recurring_paused", "recurring_id"), (recurring_id, paused));
+}
+
+pub fn emit_amendment_proposed(env: &Env, split_id: u64, total_amount: i128) {
+    env.events().publish(
+        ("amendment", "proposed", "split_id"),
+        (split_id, total_amount),
+    );
+}
+
+pub fn emit_amendment_approved(env: &Env, split_id: u64, participant: &Address) {
+    env.events().publish(
+        ("amendment", "approved", "split_id"),
+        (split_id, participant.clone()),
+    );
+}
+
+pub fn emit_amendment_applied(env: &Env, split_id: u64, total_amount: i128, refunded: i128) {
+    env.events().publish(
+        ("amendment", "applied", "split_id"),
+        (split_id, total_amount, refunded),
+    );
+}
+
+pub fn emit_manager_updated(
+    env: &Env,
+    split_id: u64,
+    manager: &Address,
+    permissions: &Vec<Permission>,
+) {
+    env.events().publish(
+        (
+            Symbol::new(env, "ManagerUpdated"),
+            split_id,
+            manager.clone(),
+        ),
+        permissions.clone(),
+    );
+}
+
+pub fn emit_creator_proposed(env: &Env, split_id: u64, creator: &Address, proposed: &Address) {
+    env.events().publish(
+        (
+            Symbol::new(env, "CreatorProposed"),
+            split_id,
+            creator.clone(),
+        ),
+        proposed.clone(),
+    );
+}
+
+pub fn emit_creator_transferred(env: &Env, split_id: u64, previous: &Address, creator: &Address) {
+    env.events().publish(
+        (
+            Symbol::new(env, "CreatorTransferred"),
+            split_id,
+            previous.clone(),
+        ),
+        creator.clone(),
+    );
+}
+
+pub fn emit_obligation_transferred(
+    env: &Env,
+    split_id: u64,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+    balance_moved: i128,
+) {
+    env.events().publish(
+        ("obligation", "split_id", "from"),
+        (split_id, from.clone(), to.clone(), amount, balance_moved),
+    );
+}
+
 pub fn emit_deposit(env: &Env, split_id: u64, participant: &Address, amount: i128) {
     env.events().publish(
         ("deposit", "split_id", "participant"),
@@ -27,6 +223,69 @@ pub fn emit_deposit(env: &Env, split_id: u64, participant: &Address, amount: i12
     );
 }
 
+pub fn emit_converted_deposit(
+    env: &Env,
+    split_id: u64,
+    participant: &Address,
+    source_asset: &Address,
+    source_amount: i128,
+    credited: i128,
+    refunded: i128,
+) {
+    env.events().publish(
+        ("conversion", "split_id", "participant"),
+        (
+            split_id,
+            participant.clone(),
+            source_asset.clone(),
+            source_amount,
+            credited,
+            refunded,
+        ),
+    );
+}
+
+pub fn emit_intent_relayed(
+    env: &Env,
+    split_id: u64,
+    participant: &Address,
+    nonce: u64,
+    relayer: &Address,
+    relayer_fee: i128,
+) {
+    env.events().publish(
+        ("intent", "split_id", "participant"),
+        (
+            split_id,
+            participant.clone(),
+            nonce,
+            relayer.clone(),
+            relayer_fee,
+        ),
+    );
+}
+
+pub fn emit_withdrawal(env: &Env, split_id: u64, participant: &Address, amount: i128) {
+    env.events().publish(
+        ("withdrawal", "split_id", "participant"),
+        (split_id, participant.clone(), amount),
+    );
+}
+
+pub fn emit_payout(env: &Env, split_id: u64, recipient: &Address, amount: i128) {
+    env.events().publish(
+        ("payout", "split_id", "recipient"),
+        (split_id, recipient.clone(), amount),
+    );
+}
+
+pub fn emit_milestone_released(env: &Env, split_id: u64, index: u32, amount: i128) {
+    env.events().publish(
+        ("milestone", "split_id", "index"),
+        (split_id, index, amount),
+    );
+}
+
 pub fn emit_released(env: &Env, split_id: u64, released_amount: i128) {
     env.events()
         .publish(("released", "split_id"), (split_id, released_amount));
@@ -36,10 +295,44 @@ pub fn emit_cancelled(env: &Env, split_id: u64) {
     env.events().publish(("cancelled", "split_id"), split_id);
 }
 
-pub fn emit_fees_collected(env: &Env, amount: i128, treasury: &Address) {
+pub fn emit_expired(env: &Env, split_id: u64, refunded_amount: i128) {
+    env.events()
+        .publish(("expired", "split_id"), (split_id, refunded_amount));
+}
+
+pub fn emit_staked(env: &Env, split_id: u64, amount: i128) {
+    env.events()
+        .publish(("yield", "staked", "split_id"), (split_id, amount));
+}
+
+pub fn emit_unstaking(env: &Env, split_id: u64, amount: i128, exit: &SplitStatus) {
+    env.events().publish(
+        ("yield", "unstaking", "split_id"),
+        (split_id, amount, exit.clone()),
+    );
+}
+
+pub fn emit_yield_paid(env: &Env, split_id: u64, amount: i128, policy: &YieldPolicy) {
+    env.events()
+        .publish(("yield", "paid", "split_id"), (split_id, amount, *policy));
+}
+
+pub fn emit_fees_collected(env: &Env, amount: i128, treasury: &Address, applied: &AppliedFee) {
     env.events().publish(
         (Symbol::new(env, "FeesCollected"),),
-        (amount, treasury.clone()),
+        (amount, treasury.clone(), applied.clone()),
+    );
+}
+
+pub fn emit_fee_schedule_updated(env: &Env, schedule: &FeeSchedule) {
+    env.events()
+        .publish((Symbol::new(env, "FeeScheduleUpdated"),), schedule.clone());
+}
+
+pub fn emit_creator_fee_updated(env: &Env, creator: &Address, fee_bps: Option<u32>) {
+    env.events().publish(
+        (Symbol::new(env, "CreatorFeeUpdated"), creator.clone()),
+        fee_bps,
     );
 }
 
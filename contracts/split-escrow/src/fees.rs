@@ -1,20 +1,20 @@
 use soroban_sdk::{token, Address, Env};
 
-use crate::{errors::Error, events, storage};
+use crate::{
+    access,
+    errors::Error,
+    events, storage,
+    types::{AppliedFee, FeeSchedule, Role},
+};
 
-const MAX_BPS: u32 = 10_000;
+pub(crate) const MAX_BPS: u32 = 10_000;
 
-fn require_admin(env: &Env) -> Result<(), Error> {
-    if !storage::has_admin(env) {
-        return Err(Error::NotInitialized);
-    }
-    let admin = storage::get_admin(env);
-    admin.require_auth();
-    Ok(())
+fn require_fee_manager(env: &Env, caller: &Address) -> Result<(), Error> {
+    access::require_role(env, caller, Role::FeeManager)
 }
 
-pub fn set_fee(env: &Env, fee_bps: u32) -> Result<(), Error> {
-    require_admin(env)?;
+pub fn set_fee(env: &Env, caller: &Address, fee_bps: u32) -> Result<(), Error> {
+    require_fee_manager(env, caller)?;
     if fee_bps > MAX_BPS {
         return Err(Error::InvalidFeeBps);
     }
@@ -22,8 +22,64 @@ pub fn set_fee(env: &Env, fee_bps: u32) -> Result<(), Error> {
     Ok(())
 }
 
-pub fn set_treasury(env: &Env, treasury: &Address) -> Result<(), Error> {
-    require_admin(env)?;
+/// Replace the fee schedule. Tier thresholds must start at 0 and strictly increase, every
+/// rate must be within `MAX_BPS`, and `0 <= min_fee <= max_fee`.
+pub fn set_fee_schedule(env: &Env, caller: &Address, schedule: &FeeSchedule) -> Result<(), Error> {
+    require_fee_manager(env, caller)?;
+    validate_schedule(schedule)?;
+    storage::set_fee_schedule(env, schedule);
+    events::emit_fee_schedule_updated(env, schedule);
+    Ok(())
+}
+
+fn validate_schedule(schedule: &FeeSchedule) -> Result<(), Error> {
+    if schedule.min_fee < 0 {
+        return Err(Error::InvalidFeeSchedule);
+    }
+    if let Some(max_fee) = schedule.max_fee {
+        if max_fee < schedule.min_fee {
+            return Err(Error::InvalidFeeSchedule);
+        }
+    }
+
+    let mut i = 0u32;
+    while i < schedule.tiers.len() {
+        let tier = schedule.tiers.get(i).unwrap();
+        if tier.fee_bps > MAX_BPS {
+            return Err(Error::InvalidFeeBps);
+        }
+        if i == 0 && tier.min_amount != 0 {
+            return Err(Error::InvalidFeeSchedule);
+        }
+        if i > 0 && tier.min_amount <= schedule.tiers.get(i - 1).unwrap().min_amount {
+            return Err(Error::InvalidFeeSchedule);
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+/// Set (`Some`) or clear (`None`) a per-creator rate that replaces the schedule entirely,
+/// including its min/max bounds.
+pub fn set_creator_fee(
+    env: &Env,
+    caller: &Address,
+    creator: &Address,
+    fee_bps: Option<u32>,
+) -> Result<(), Error> {
+    require_fee_manager(env, caller)?;
+    if let Some(bps) = fee_bps {
+        if bps > MAX_BPS {
+            return Err(Error::InvalidFeeBps);
+        }
+    }
+    storage::set_creator_fee_bps(env, creator, fee_bps);
+    events::emit_creator_fee_updated(env, creator, fee_bps);
+    Ok(())
+}
+
+pub fn set_treasury(env: &Env, caller: &Address, treasury: &Address) -> Result<(), Error> {
+    require_fee_manager(env, caller)?;
     storage::set_treasury(env, treasury);
     Ok(())
 }
@@ -32,17 +88,55 @@ pub fn calculate_fee(total: i128, fee_bps: u32) -> i128 {
     (total * fee_bps as i128) / MAX_BPS as i128
 }
 
-pub fn collect_fee(env: &Env, total: i128) -> Result<i128, Error> {
-    let fee_bps = storage::get_fee_bps(env);
+/// Price a release of `amount` from a split created by `creator` whose total deposits are
+/// `volume`. The creator override wins; otherwise the highest tier whose threshold `volume`
+/// reaches (or the flat rate when no tiers exist), clamped to the schedule's bounds.
+pub fn quote_fee(env: &Env, creator: &Address, volume: i128, amount: i128) -> (i128, AppliedFee) {
+    if let Some(bps) = storage::get_creator_fee_bps(env, creator) {
+        return (calculate_fee(amount, bps), AppliedFee::CreatorOverride);
+    }
+
+    let schedule = storage::get_fee_schedule(env);
+    let mut fee_bps = storage::get_fee_bps(env);
+    let mut applied = AppliedFee::Flat;
+    if let Some(schedule) = &schedule {
+        let mut i = 0u32;
+        while i < schedule.tiers.len() {
+            let tier = schedule.tiers.get(i).unwrap();
+            if volume < tier.min_amount {
+                break;
+            }
+            fee_bps = tier.fee_bps;
+            applied = AppliedFee::Tier(i);
+            i += 1;
+        }
+    }
+
+    let mut fee_amount = calculate_fee(amount, fee_bps);
+    if let Some(schedule) = schedule {
+        fee_amount = fee_amount.max(schedule.min_fee);
+        if let Some(max_fee) = schedule.max_fee {
+            fee_amount = fee_amount.min(max_fee);
+        }
+    }
+    (fee_amount.min(amount), applied)
+}
+
+pub fn collect_fee(
+    env: &Env,
+    token: &Address,
+    creator: &Address,
+    volume: i128,
+    total: i128,
+) -> Result<i128, Error> {
     let treasury = storage::get_treasury(env).ok_or(Error::TreasuryNotSet)?;
-    let fee_amount = calculate_fee(total, fee_bps);
+    let (fee_amount, applied) = quote_fee(env, creator, volume, total);
 
     if fee_amount > 0 {
-        let token = storage::get_token(env);
-        let token_client = token::Client::new(env, &token);
+        let token_client = token::Client::new(env, token);
         token_client.transfer(&env.current_contract_address(), &treasury, &fee_amount);
     }
 
-    events::emit_fees_collected(env, fee_amount, &treasury);
+    events::emit_fees_collected(env, fee_amount, &treasury, &applied);
     Ok(fee_amount)
 }
@@ -2,8 +2,7 @@
 ///
 /// This module defines the single unambiguous input type accepted by
 /// `SplitEscrowContract::create_escrow`, eliminating the conflicting duplicate
-/// `metadata` parameters and the ignored `whitelist_enabled` flag that existed
-/// in the previous signature.
+/// `metadata` parameters that existed in the previous signature.
 ///
 /// ## Design decisions
 ///
@@ -14,14 +13,20 @@
 /// * `note` remains `Option<String>` because `None` and an empty string have
 ///   the same storage representation and callers should be explicit.
 ///
-/// * `whitelist_enabled` is removed from the create call.  Whitelist state is
-///   managed after creation via `toggle_whitelist` / `add_to_whitelist` /
-///   `remove_from_whitelist`.  Initialising it at creation time was confusing
-///   because the flag was immediately overwritten with `false` in the old code.
+/// * `whitelist_enabled` sets the initial whitelist state.  It can be changed
+///   after creation via `toggle_whitelist`, and entries are managed with
+///   `add_to_whitelist` / `remove_from_whitelist`.
 ///
 /// * `max_participants` stays as `Option<u32>`; `None` means "use the
-///   contract-level default of 50".
-use soroban_sdk::{contracttype, Address, Map, String};
+///   admin-configured limit" (50 unless changed with `set_limits`), which also
+///   caps explicit values.
+///
+/// * Soroban caps contract functions at ten parameters, so optional settings
+///   (`payouts`, `deadline`, ...) are added here as fields rather than as new
+///   positional arguments.
+use soroban_sdk::{contracttype, Address, Map, String, Vec};
+
+use crate::types::{Payout, YieldPolicy};
 
 /// Input type for `create_escrow`.
 ///
@@ -41,21 +46,46 @@ pub struct CreateEscrowParams {
     /// Must be positive and equal to the sum of all obligation values.
     pub total_amount: i128,
 
+    /// Asset to escrow.  Must be on the admin-managed token allowlist.
+    pub token: Address,
+
     /// Per-participant expected contribution amounts.
     /// `sum(obligations.values()) == total_amount` is enforced on creation.
     pub obligations: Map<Address, i128>,
 
-    /// Optional upper bound on distinct depositing participants.
-    /// Defaults to the contract constant `DEFAULT_MAX_PARTICIPANTS` (50) when
-    /// `None`.
+    /// Optional release distribution table.  Entries are either all basis
+    /// points (summing to 10_000) or all fixed amounts (summing to
+    /// `total_amount`).  Empty means the creator receives the full release.
+    pub payouts: Vec<Payout>,
+
+    /// Optional ordered tranche amounts for staged release via
+    /// `release_milestone`.  Each must be positive and together they must sum
+    /// to `total_amount`.  Empty means the escrow is released in one go.
+    pub milestones: Vec<i128>,
+
+    /// Optional upper bound on distinct depositing participants, at most
+    /// `Limits::max_participants` (50 unless changed by the admin), which
+    /// is also the default when `None`.
     pub max_participants: Option<u32>,
 
-    /// Arbitrary key/value pairs attached to the escrow (max 32 entries, each
-    /// key and value ≤ 128 bytes).  Pass an empty map when no metadata is
+    /// When `true`, only whitelisted addresses may deposit.
+    pub whitelist_enabled: bool,
+
+    /// Arbitrary key/value pairs attached to the escrow (by default max 32
+    /// entries, each key and value ≤ 128 bytes; see `get_limits`).  Pass an empty map when no metadata is
     /// needed.
     pub metadata: Map<String, String>,
 
-    /// Short on-chain context string (≤ 128 bytes).  `None` stores an empty
+    /// Short on-chain context string (≤ 128 bytes by default).  `None` stores an empty
     /// string; callers should use `set_note` to update it after creation.
     pub note: Option<String>,
+
+    /// Optional ledger timestamp after which a still-`Pending` escrow can be
+    /// expired by anyone via `expire_split`, refunding all deposits.
+    pub deadline: Option<u64>,
+
+    /// Opt into yield mode: held funds can be staked through the registered
+    /// staking contract via `stake_idle`, with rewards going to depositors or the
+    /// creator per this policy.  `Disabled` keeps funds idle in the escrow.
+    pub yield_policy: YieldPolicy,
 }
@@ -1,24 +1,40 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, token, Address, Env, Map, String, Vec};
+use soroban_sdk::{contract, contractimpl, token, Address, BytesN, Env, IntoVal, Map, String, Vec};
 
+mod access;
+mod amendments;
+mod batch;
+mod conversion;
+mod disputes;
 mod errors;
 mod events;
 mod fees;
+mod interface;
+mod limits;
+mod managers;
+mod migration;
+mod payouts;
+mod queries;
+mod receipts;
+mod recurring;
+mod staking;
+mod state_machine;
 mod storage;
 mod test;
 mod types;
 
 pub use crate::errors::Error;
-pub use crate::types::{Split, SplitStatus};
-
-const DEFAULT_MAX_PARTICIPANTS: u32 = 50;
-const MAX_NOTE_LEN: u32 = 128;
-const MAX_METADATA_ENTRIES: u32 = 32;
-const MAX_METADATA_STRING_LEN: u32 = 128;
-
-fn validate_note_len(note: &String) -> Result<(), Error> {
-    if note.len() > MAX_NOTE_LEN {
+pub use crate::interface::CreateEscrowParams;
+pub use crate::types::{
+    Amendment, AppliedFee, BatchOutcome, CycleLink, DepositIntent, DisputeHold, FeeSchedule,
+    FeeTier, Limits, PathAsset, Payout, PayoutShare, Permission, ReceiptLine, RecurringSplit, Role,
+    SettlementReceipt, SettlementSummary, Split, SplitIdPage, SplitStatus, StakingConfig,
+    StatusStamp, TtlPolicy, YieldPolicy, YieldPool, YieldPosition,
+};
+
+fn validate_note_len(env: &Env, note: &String) -> Result<(), Error> {
+    if note.len() > limits::get(env).max_note_len {
         return Err(Error::InvalidInput);
     }
     Ok(())
@@ -35,8 +51,20 @@ fn participant_known(participants: &Vec<Address>, addr: &Address) -> bool {
     false
 }
 
-fn validate_metadata(metadata: &Map<String, String>) -> Result<(), Error> {
-    if metadata.len() > MAX_METADATA_ENTRIES {
+fn remove_participant(participants: &mut Vec<Address>, addr: &Address) {
+    let mut i = 0u32;
+    while i < participants.len() {
+        if participants.get(i).unwrap() == *addr {
+            participants.remove(i);
+            return;
+        }
+        i += 1;
+    }
+}
+
+fn validate_metadata(env: &Env, metadata: &Map<String, String>) -> Result<(), Error> {
+    let limits = limits::get(env);
+    if metadata.len() > limits.max_metadata_entries {
         return Err(Error::InvalidMetadata);
     }
 
@@ -45,7 +73,9 @@ fn validate_metadata(metadata: &Map<String, String>) -> Result<(), Error> {
     while i < keys.len() {
         let key = keys.get(i).unwrap();
         let value = metadata.get(key.clone()).unwrap();
-        if key.len() > MAX_METADATA_STRING_LEN || value.len() > MAX_METADATA_STRING_LEN {
+        if key.len() > limits.max_metadata_string_len
+            || value.len() > limits.max_metadata_string_len
+        {
             return Err(Error::InvalidMetadata);
         }
         i += 1;
@@ -54,8 +84,311 @@ fn validate_metadata(metadata: &Map<String, String>) -> Result<(), Error> {
     Ok(())
 }
 
-fn is_active(status: &SplitStatus) -> bool {
-    *status != SplitStatus::Released && *status != SplitStatus::Cancelled
+fn validate_milestones(milestones: &Vec<i128>, total_amount: i128) -> Result<(), Error> {
+    if milestones.is_empty() {
+        return Ok(());
+    }
+    let mut sum = 0i128;
+    let mut i = 0u32;
+    while i < milestones.len() {
+        let amount = milestones.get(i).unwrap();
+        if amount <= 0 {
+            return Err(Error::InvalidMilestones);
+        }
+        sum += amount;
+        i += 1;
+    }
+    if sum != total_amount {
+        return Err(Error::InvalidMilestones);
+    }
+    Ok(())
+}
+
+/// Take the fee on `amount` and pay the rest out through the split's payout table.
+/// Returns the amount paid to recipients.
+fn release_tranche(env: &Env, split: &mut Split, amount: i128) -> Result<i128, Error> {
+    if staking::locked(env, split.split_id) > 0 {
+        return Err(Error::FundsStaked);
+    }
+    let fee_amount = fees::collect_fee(
+        env,
+        &split.token,
+        &split.creator,
+        split.deposited_amount,
+        amount,
+    )?;
+    let net_amount = amount - fee_amount;
+    let paid = payouts::distribute(env, split, net_amount);
+    if let Some(treasury) = storage::get_treasury(env) {
+        receipts::record_release(env, split.split_id, fee_amount, &treasury, &paid);
+    }
+    split.released_amount += amount;
+    Ok(net_amount)
+}
+
+/// Check that `participant` may deposit `amount` into `split` as it stands.
+fn check_deposit(
+    env: &Env,
+    split: &Split,
+    participant: &Address,
+    amount: i128,
+) -> Result<(), Error> {
+    if amount <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+    if !state_machine::accepts_deposits(&split.status) {
+        return Err(Error::SplitNotPending);
+    }
+    if deadline_passed(env, &split.deadline) {
+        return Err(Error::DeadlinePassed);
+    }
+
+    if storage::is_whitelist_enabled(env, split.split_id)
+        && !storage::is_whitelisted(env, split.split_id, participant)
+    {
+        return Err(Error::Unauthorized);
+    }
+
+    let obligation = split
+        .obligations
+        .get(participant.clone())
+        .ok_or(Error::ParticipantNotOwed)?;
+    if obligation <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    if split.deposited_amount + amount > split.total_amount {
+        return Err(Error::InvalidAmount);
+    }
+
+    let previous_balance = split.balances.get(participant.clone()).unwrap_or(0i128);
+    if previous_balance + amount > obligation {
+        return Err(Error::InvalidAmount);
+    }
+
+    if !participant_known(&split.participants, participant)
+        && split.participants.len() >= split.max_participants
+    {
+        return Err(Error::ParticipantCapExceeded);
+    }
+    Ok(())
+}
+
+/// Validate a deposit of `amount` by `participant` and record it on `split`, moving the
+/// split to `Ready` once fully funded. The caller moves the tokens and persists `split`.
+fn credit_deposit(
+    env: &Env,
+    split: &mut Split,
+    participant: &Address,
+    amount: i128,
+) -> Result<(), Error> {
+    check_deposit(env, split, participant, amount)?;
+
+    if !participant_known(&split.participants, participant) {
+        split.participants.push_back(participant.clone());
+    }
+    let previous_balance = split.balances.get(participant.clone()).unwrap_or(0i128);
+    split
+        .balances
+        .set(participant.clone(), previous_balance + amount);
+    split.deposited_amount += amount;
+    if split.deposited_amount == split.total_amount {
+        state_machine::apply(env, split, SplitStatus::Ready)?;
+    }
+    Ok(())
+}
+
+/// Pay out everything still held, including unpaid milestone tranches, and mark `split`
+/// released. The caller checks status and authorization and persists `split`.
+fn settle(env: &Env, split: &mut Split) -> Result<i128, Error> {
+    let remaining = split.deposited_amount - split.released_amount;
+    let released_amount = release_tranche(env, split, remaining)?;
+    split.milestones_released = split.milestones.len();
+    state_machine::apply(env, split, SplitStatus::Released)?;
+    Ok(released_amount)
+}
+
+/// Refund what `split` still holds, pro rata over deposited balances, and mark it
+/// cancelled. Before any milestone release this is exactly each participant's balance;
+/// the rounding remainder goes to the first participant refunded.
+fn refund_remaining(env: &Env, split: &mut Split) -> Result<(), Error> {
+    let token_client = token::Client::new(env, &split.token);
+    let remaining = split.deposited_amount - split.released_amount;
+    let participants_len = split.participants.len();
+    let mut refunded = 0i128;
+    let mut i = 0u32;
+    while i < participants_len {
+        let participant = split.participants.get(i).unwrap();
+        let balance = split.balances.get(participant).unwrap_or(0i128);
+        if balance > 0 {
+            refunded += balance * remaining / split.deposited_amount;
+        }
+        i += 1;
+    }
+    let mut dust = remaining - refunded;
+
+    let deposits = split.balances.clone();
+    let mut refunds = Map::new(env);
+    i = 0;
+    while i < participants_len {
+        let participant = split.participants.get(i).unwrap();
+        let balance = split.balances.get(participant.clone()).unwrap_or(0i128);
+        if balance > 0 {
+            let amount = balance * remaining / split.deposited_amount + dust;
+            dust = 0;
+            if amount > 0 {
+                token_client.transfer(&env.current_contract_address(), &participant, &amount);
+            }
+            refunds.set(participant.clone(), amount);
+            // Zero out balances to prevent accidental double-refund.
+            split.balances.set(participant, 0i128);
+        }
+        i += 1;
+    }
+    receipts::record_refunds(env, split.split_id, &deposits, &refunds);
+
+    // Clear participants list; split is now cancelled and cannot be released.
+    split.participants = Vec::new(env);
+    split.deposited_amount = 0;
+    state_machine::apply(env, split, SplitStatus::Cancelled)
+}
+
+/// Refund every deposited balance of `split` in full and mark it expired. Returns the
+/// total refunded.
+fn refund_expired(env: &Env, split: &mut Split) -> Result<i128, Error> {
+    let token_client = token::Client::new(env, &split.token);
+
+    let mut refunded = 0i128;
+    let deposits = split.balances.clone();
+    let depositors = split.balances.keys();
+    let mut i = 0u32;
+    while i < depositors.len() {
+        let participant = depositors.get(i).unwrap();
+        let amount = split.balances.get(participant.clone()).unwrap_or(0i128);
+        if amount > 0 {
+            token_client.transfer(&env.current_contract_address(), &participant, &amount);
+            split.balances.set(participant, 0i128);
+            refunded += amount;
+        }
+        i += 1;
+    }
+    receipts::record_refunds(env, split.split_id, &deposits, &deposits);
+
+    split.participants = Vec::new(env);
+    split.deposited_amount = 0;
+    state_machine::apply(env, split, SplitStatus::Expired)?;
+    Ok(refunded)
+}
+
+/// Every obligation must be positive and together they must equal `total_amount`.
+fn validate_obligations(obligations: &Map<Address, i128>, total_amount: i128) -> Result<(), Error> {
+    let mut sum_obligations = 0i128;
+    for (_, val) in obligations.iter() {
+        if val <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        sum_obligations += val;
+    }
+    if sum_obligations != total_amount {
+        return Err(Error::TotalAmountMismatch);
+    }
+    Ok(())
+}
+
+/// Validate `params` and store a new `Pending` split. Callers handle authorization.
+fn open_split(env: &Env, params: CreateEscrowParams) -> Result<u64, Error> {
+    let CreateEscrowParams {
+        creator,
+        description,
+        total_amount,
+        token,
+        obligations,
+        payouts,
+        milestones,
+        max_participants,
+        whitelist_enabled,
+        metadata,
+        note,
+        deadline,
+        yield_policy,
+    } = params;
+
+    if total_amount <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+    if !storage::is_token_allowed(env, &token) {
+        return Err(Error::TokenNotAllowed);
+    }
+
+    validate_obligations(&obligations, total_amount)?;
+    payouts::validate(&payouts, total_amount)?;
+    validate_milestones(&milestones, total_amount)?;
+
+    if deadline_passed(env, &deadline) {
+        return Err(Error::DeadlinePassed);
+    }
+
+    let max_cap = limits::get(env).max_participants;
+    let cap = max_participants.unwrap_or(max_cap);
+    if cap == 0 || cap > max_cap {
+        return Err(Error::InvalidInput);
+    }
+
+    validate_metadata(env, &metadata)?;
+
+    let note_stored = match note {
+        Some(n) => {
+            validate_note_len(env, &n)?;
+            n
+        }
+        None => String::from_str(env, ""),
+    };
+
+    let split_id = storage::get_next_split_id(env);
+    storage::bump_next_split_id(env);
+
+    let participants = Vec::new(env);
+
+    let split = Split {
+        split_id,
+        creator,
+        description,
+        token,
+        metadata,
+        total_amount,
+        deposited_amount: 0,
+        status: SplitStatus::Pending,
+        max_participants: cap,
+        participants,
+        balances: Map::new(env),
+        obligations,
+        note: note_stored,
+        deadline,
+        payouts,
+        milestones,
+        milestones_released: 0,
+        released_amount: 0,
+    };
+    if yield_policy != YieldPolicy::Disabled {
+        staking::open_position(env, split_id, &split.token, yield_policy)?;
+    }
+    storage::set_split(env, &split);
+    storage::set_whitelist_enabled(env, split_id, whitelist_enabled);
+    storage::index_by_creator(env, &split.creator, split_id);
+    for participant in split.obligations.keys().iter() {
+        storage::index_by_participant(env, &participant, split_id);
+    }
+    storage::index_by_status(env, &split.status, split_id);
+    receipts::record_status(env, split_id, &split.status);
+    events::emit_split_created(env, &split);
+    Ok(split_id)
+}
+
+fn deadline_passed(env: &Env, deadline: &Option<u64>) -> bool {
+    match deadline {
+        Some(d) => env.ledger().timestamp() > *d,
+        None => false,
+    }
 }
 
 #[contract]
@@ -63,12 +396,15 @@ pub struct SplitEscrowContract;
 
 #[contractimpl]
 impl SplitEscrowContract {
+    /// `token_address` becomes the first asset on the allowlist; more can be added
+    /// with `set_token_allowed`.
     pub fn initialize(
         env: Env,
         admin: Address,
         token_address: Address,
         version: String,
     ) -> Result<(), Error> {
+        storage::bump_instance(&env);
         if storage::has_admin(&env) {
             return Err(Error::AlreadyInitialized);
         }
@@ -77,18 +413,54 @@ impl SplitEscrowContract {
         validate_version(&version)?;
 
         storage::set_admin(&env, &admin);
-        storage::set_token(&env, &token_address);
+        storage::set_token_allowed(&env, &token_address, true);
         storage::set_fee_bps(&env, 0u32);
         storage::set_version(&env, &version);
+        storage::set_schema_version(&env, migration::CURRENT_SCHEMA_VERSION);
         events::emit_initialized(&env, &admin);
         Ok(())
     }
 
     pub fn get_version(env: Env) -> String {
+        storage::bump_instance(&env);
         storage::get_version(&env)
     }
 
+    /// Admin-only: replace the contract code with the uploaded `new_wasm_hash` and record
+    /// `new_version`. Stored splits are rewritten afterwards by calling `migrate` until it
+    /// returns `true`; a further upgrade is refused until then.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>, new_version: String) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_admin(&env)?;
+        validate_version(&new_version)?;
+        if storage::get_schema_version(&env) != migration::CURRENT_SCHEMA_VERSION {
+            return Err(Error::MigrationPending);
+        }
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        let old_version = storage::get_version(&env);
+        storage::set_version(&env, &new_version);
+        events::emit_contract_upgraded(&env, old_version, new_version);
+        Ok(())
+    }
+
+    /// Admin-only: migrate up to `batch_size` (max 50) stored splits to the current schema.
+    /// Idempotent; returns `true` once every split has been migrated.
+    pub fn migrate(env: Env, batch_size: u32) -> Result<bool, Error> {
+        storage::bump_instance(&env);
+        access::require_admin(&env)?;
+        migration::migrate(&env, batch_size)
+    }
+
+    pub fn get_schema_version(env: Env) -> u32 {
+        storage::bump_instance(&env);
+        storage::get_schema_version(&env)
+    }
+
+    /// Record a new version string without swapping code. Prefer `upgrade`.
     pub fn upgrade_version(env: Env, new_version: String) -> Result<(), Error> {
+        storage::bump_instance(&env);
         let admin = storage::get_admin(&env);
         admin.require_auth();
 
@@ -100,85 +472,114 @@ impl SplitEscrowContract {
         Ok(())
     }
 
-    /// Create an escrow split. If `max_participants` is `None`, the cap defaults to 50.
-    /// `metadata` must satisfy map size and string length limits. If `note` is `None`, note is empty.
-    pub fn create_escrow(
+    /// Admin-only: add or remove an asset from the set new splits may escrow.
+    /// Existing splits keep settling in their original asset.
+    pub fn set_token_allowed(env: Env, token: Address, allowed: bool) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_admin(&env)?;
+        storage::set_token_allowed(&env, &token, allowed);
+        events::emit_token_allowlist_updated(&env, &token, allowed);
+        Ok(())
+    }
+
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        storage::bump_instance(&env);
+        storage::is_token_allowed(&env, &token)
+    }
+
+    /// Create an escrow split from `CreateEscrowParams`.
+    ///
+    /// If `max_participants` is `None`, the cap is the admin-configured participant limit
+    /// (see `get_limits`); an explicit cap must be positive and within it. `token` must be on
+    /// the admin allowlist. `payouts` is an optional distribution table for `release_funds`; pass an empty
+    /// vector to pay the creator. `metadata` must satisfy map size and string length limits. If
+    /// `note` is `None`, note is empty. If `deadline` is set, the split can be expired and
+    /// refunded once the ledger passes it. If `milestones` is non-empty, funds can be paid out
+    /// tranche by tranche with `release_milestone`. If `whitelist_enabled` is set, only
+    /// whitelisted addresses can deposit.
+    pub fn create_escrow(env: Env, params: CreateEscrowParams) -> Result<u64, Error> {
+        storage::bump_instance(&env);
+        if !storage::has_admin(&env) {
+            return Err(Error::NotInitialized);
+        }
+        params.creator.require_auth();
+        access::require_not_paused(&env)?;
+        open_split(&env, params)
+    }
+
+    /// Register a schedule that opens a new split every `period` seconds, `cycles` times.
+    /// Each cycle owes the same `obligations` (summing to `total_amount`) and pays the creator.
+    /// The first cycle is due immediately; cycles are opened by the permissionless
+    /// `roll_cycle`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_recurring(
         env: Env,
         creator: Address,
         description: String,
+        token: Address,
         total_amount: i128,
-        metadata: Map<String, String>,
         obligations: Map<Address, i128>,
-        max_participants: Option<u32>,
-        whitelist_enabled: bool,
-        note: Option<String>,
+        period: u64,
+        cycles: u32,
     ) -> Result<u64, Error> {
+        storage::bump_instance(&env);
         if !storage::has_admin(&env) {
             return Err(Error::NotInitialized);
         }
         creator.require_auth();
-        if total_amount <= 0 {
-            return Err(Error::InvalidAmount);
-        }
-
-        // Validate that total_amount matches sum of obligations.
-        let mut sum_obligations = 0i128;
-        let keys = obligations.keys();
-        for i in 0..keys.len() {
-            let key = keys.get(i).unwrap();
-            let val = obligations.get(key).unwrap();
-            if val <= 0 {
-                return Err(Error::InvalidAmount);
-            }
-            sum_obligations += val;
-        }
-        if sum_obligations != total_amount {
-            return Err(Error::TotalAmountMismatch);
-        }
-
-        let cap = max_participants.unwrap_or(DEFAULT_MAX_PARTICIPANTS);
+        access::require_not_paused(&env)?;
+        recurring::create(
+            &env,
+            creator,
+            description,
+            token,
+            total_amount,
+            obligations,
+            period,
+            cycles,
+        )
+    }
 
-        validate_metadata(&metadata)?;
+    /// Open the next cycle of a schedule once it is due. Anyone may call this.
+    pub fn roll_cycle(env: Env, recurring_id: u64) -> Result<u64, Error> {
+        storage::bump_instance(&env);
+        recurring::roll(&env, recurring_id)
+    }
 
-        let note_stored = match note {
-            Some(n) => {
-                validate_note_len(&n)?;
-                n
-            }
-            None => String::from_str(&env, ""),
-        };
+    /// Creator-only: stop or restart rolling new cycles. Existing cycle splits are unaffected.
+    pub fn set_recurring_paused(env: Env, recurring_id: u64, paused: bool) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        recurring::set_paused(&env, recurring_id, paused)
+    }
 
-        let split_id = storage::get_next_split_id(&env);
-        storage::bump_next_split_id(&env);
+    pub fn get_recurring(env: Env, recurring_id: u64) -> Result<RecurringSplit, Error> {
+        storage::bump_instance(&env);
+        storage::get_recurring(&env, recurring_id).ok_or(Error::RecurringNotFound)
+    }
 
-        let participants = Vec::new(&env);
+    /// Split ids opened by a schedule so far, oldest first.
+    pub fn get_cycle_history(env: Env, recurring_id: u64) -> Result<Vec<u64>, Error> {
+        storage::bump_instance(&env);
+        let recurring =
+            storage::get_recurring(&env, recurring_id).ok_or(Error::RecurringNotFound)?;
+        Ok(recurring.history)
+    }
 
-        let split = Split {
-            split_id,
-            creator,
-            description,
-            metadata,
-            total_amount,
-            deposited_amount: 0,
-            status: SplitStatus::Pending,
-            max_participants: cap,
-            participants,
-            balances: Map::new(&env),
-            obligations,
-            note: note_stored,
-        };
-        storage::set_split(&env, &split);
-        storage::set_whitelist_enabled(&env, split_id, whitelist_enabled);
-        events::emit_split_created(&env, &split);
-        Ok(split_id)
+    /// Schedule position of `split_id`, or `None` if it was not opened by a schedule.
+    pub fn get_cycle_link(env: Env, split_id: u64) -> Option<CycleLink> {
+        storage::bump_instance(&env);
+        storage::get_cycle_link(&env, split_id)
     }
 
-    /// Creator-only: update the on-chain note while the escrow is active (Pending or Ready).
-    pub fn set_note(env: Env, split_id: u64, note: String) -> Result<(), Error> {
-        validate_note_len(&note)?;
+    /// Update the on-chain note while the escrow is active. `caller` must be the creator or
+    /// a co-manager with `EditNote`.
+    pub fn set_note(env: Env, caller: Address, split_id: u64, note: String) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
+        validate_note_len(&env, &note)?;
         let mut split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
-        split.creator.require_auth();
-        if !is_active(&split.status) {
+        managers::require_permission(&env, &split, &caller, Permission::EditNote)?;
+        if !state_machine::is_active(&split.status) {
             return Err(Error::SplitNotActive);
         }
         if split.note == note {
@@ -190,45 +591,61 @@ impl SplitEscrowContract {
         Ok(())
     }
 
-    /// Cancel a split and refund all deposited participant balances.
+    /// Cancel a split and refund all deposited participant balances. `caller` must be the
+    /// creator or a co-manager with `Cancel`.
     /// Used when a dispute is upheld (raiser wins).
-    pub fn cancel_split(env: Env, split_id: u64) -> Result<(), Error> {
+    pub fn cancel_split(env: Env, caller: Address, split_id: u64) -> Result<(), Error> {
+        storage::bump_instance(&env);
         let mut split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
-        // Only the split creator can cancel/refund.
-        split.creator.require_auth();
+        // A disputed split is only cancelled by the dispute contract, through
+        // `reverse_split`, which has already checked that `caller` may resolve it.
+        if split.status == SplitStatus::Disputed {
+            caller.require_auth();
+            access::require_dispute_contract(&env)?;
+            storage::remove_dispute_hold(&env, split_id);
+        } else {
+            managers::require_permission(&env, &split, &caller, Permission::Cancel)?;
+        }
 
-        if split.status == SplitStatus::Released || split.status == SplitStatus::Cancelled {
+        if !state_machine::can_cancel(&split.status) {
             return Err(Error::SplitNotActive);
         }
 
-        let token_address = storage::get_token(&env);
-        let token_client = token::Client::new(&env, &token_address);
+        if staking::begin_exit(&env, &mut split, SplitStatus::Cancelled)? {
+            storage::set_split(&env, &split);
+            return Ok(());
+        }
+        refund_remaining(&env, &mut split)?;
+        storage::set_split(&env, &split);
+        events::emit_cancelled(&env, split_id);
+        Ok(())
+    }
 
-        // Refund all distinct participants.
-        let participants_len = split.participants.len();
-        let mut i = 0u32;
-        while i < participants_len {
-            let participant = split.participants.get(i).unwrap();
-            let amount = split.balances.get(participant.clone()).unwrap_or(0i128);
-            if amount > 0 {
-                token_client.transfer(&env.current_contract_address(), &participant, &amount);
-                // Zero out balances to prevent accidental double-refund.
-                split.balances.set(participant, 0i128);
-            }
-            i += 1;
+    /// Expire a `Pending` split whose deadline has passed and refund every deposited balance.
+    /// Permissionless, so participants can recover funds without the creator.
+    pub fn expire_split(env: Env, split_id: u64) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        let mut split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
+        if split.status != SplitStatus::Pending {
+            return Err(Error::SplitNotPending);
+        }
+        if !deadline_passed(&env, &split.deadline) {
+            return Err(Error::DeadlineNotReached);
         }
 
-        // Clear participants list; split is now cancelled and cannot be released.
-        split.participants = Vec::new(&env);
-        split.deposited_amount = 0;
-        split.status = SplitStatus::Cancelled;
+        if staking::begin_exit(&env, &mut split, SplitStatus::Expired)? {
+            storage::set_split(&env, &split);
+            return Ok(());
+        }
+        let refunded = refund_expired(&env, &mut split)?;
         storage::set_split(&env, &split);
-        events::emit_cancelled(&env, split_id);
+        events::emit_expired(&env, split_id, refunded);
         Ok(())
     }
 
     /// Public read of the escrow note (empty string if none was set).
     pub fn get_note(env: Env, split_id: u64) -> Result<String, Error> {
+        storage::bump_instance(&env);
         let split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
         Ok(split.note.clone())
     }
@@ -239,137 +656,866 @@ impl SplitEscrowContract {
         participant: Address,
         amount: i128,
     ) -> Result<(), Error> {
+        storage::bump_instance(&env);
         participant.require_auth();
-        if amount <= 0 {
+        access::require_split_open(&env, split_id)?;
+        let mut split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
+        credit_deposit(&env, &mut split, &participant, amount)?;
+
+        let token_client = token::Client::new(&env, &split.token);
+        token_client.transfer(&participant, &env.current_contract_address(), &amount);
+
+        storage::set_split(&env, &split);
+        events::emit_deposit(&env, split_id, &participant, amount);
+        Ok(())
+    }
+
+    /// Deposit on behalf of `participant` using their signed authorization of `intent`, so a
+    /// relayer can pay the network fee. `intent.relayer_fee` goes to `intent.relayer`; the
+    /// rest is credited exactly as `deposit` would. Each participant's nonces are used in
+    /// order.
+    pub fn deposit_with_intent(
+        env: Env,
+        participant: Address,
+        intent: DepositIntent,
+    ) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        let split_id = intent.split_id;
+        access::require_split_open(&env, split_id)?;
+        if env.ledger().timestamp() > intent.expiry {
+            return Err(Error::IntentExpired);
+        }
+        let nonce = storage::get_intent_nonce(&env, &participant);
+        if intent.nonce != nonce {
+            return Err(Error::InvalidNonce);
+        }
+        participant.require_auth_for_args((intent.clone(),).into_val(&env));
+        if intent.relayer_fee < 0 || intent.relayer_fee >= intent.amount {
             return Err(Error::InvalidAmount);
         }
 
+        let credited = intent.amount - intent.relayer_fee;
         let mut split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
-        if split.status != SplitStatus::Pending {
-            return Err(Error::SplitNotPending);
+        credit_deposit(&env, &mut split, &participant, credited)?;
+
+        let token_client = token::Client::new(&env, &split.token);
+        token_client.transfer(&participant, &env.current_contract_address(), &credited);
+        if intent.relayer_fee > 0 {
+            token_client.transfer(&participant, &intent.relayer, &intent.relayer_fee);
         }
 
-        if storage::is_whitelist_enabled(&env, split_id)
-            && !storage::is_whitelisted(&env, split_id, &participant)
-        {
-            return Err(Error::Unauthorized);
+        storage::set_intent_nonce(&env, &participant, nonce + 1);
+        storage::set_split(&env, &split);
+        events::emit_intent_relayed(
+            &env,
+            split_id,
+            &participant,
+            nonce,
+            &intent.relayer,
+            intent.relayer_fee,
+        );
+        events::emit_deposit(&env, split_id, &participant, credited);
+        Ok(())
+    }
+
+    /// Deposit in another asset: `source_amount` of `source_asset` is converted into the
+    /// split token through the registered path-payment contract, via the intermediate
+    /// `path` assets, failing if the output falls more than `max_slippage_bps` below the
+    /// quoted rate. The output is credited up to the participant's outstanding obligation
+    /// and any excess is returned to them in the split token. `source_asset` must differ
+    /// from the split token, which is deposited with `deposit`. Returns the amount credited.
+    pub fn deposit_with_conversion(
+        env: Env,
+        participant: Address,
+        split_id: u64,
+        source_asset: Address,
+        source_amount: i128,
+        path: Vec<Address>,
+        max_slippage_bps: u32,
+    ) -> Result<i128, Error> {
+        storage::bump_instance(&env);
+        participant.require_auth();
+        access::require_split_open(&env, split_id)?;
+        if source_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if max_slippage_bps > fees::MAX_BPS {
+            return Err(Error::InvalidInput);
+        }
+        let mut split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
+        if source_asset == split.token {
+            return Err(Error::InvalidInput);
+        }
+        // Reject before converting if nothing could be credited.
+        check_deposit(&env, &split, &participant, 1)?;
+
+        let this = env.current_contract_address();
+        token::Client::new(&env, &source_asset).transfer(&participant, &this, &source_amount);
+        let received = conversion::convert(
+            &env,
+            split_id,
+            &source_asset,
+            source_amount,
+            &path,
+            &split.token,
+            max_slippage_bps,
+        )?;
+
+        let obligation = split.obligations.get(participant.clone()).unwrap_or(0);
+        let balance = split.balances.get(participant.clone()).unwrap_or(0);
+        let outstanding = (obligation - balance).min(split.total_amount - split.deposited_amount);
+        let credited = received.min(outstanding);
+        credit_deposit(&env, &mut split, &participant, credited)?;
+        let refunded = received - credited;
+        if refunded > 0 {
+            token::Client::new(&env, &split.token).transfer(&this, &participant, &refunded);
+        }
+
+        storage::set_split(&env, &split);
+        events::emit_converted_deposit(
+            &env,
+            split_id,
+            &participant,
+            &source_asset,
+            source_amount,
+            credited,
+            refunded,
+        );
+        events::emit_deposit(&env, split_id, &participant, credited);
+        Ok(credited)
+    }
+
+    pub fn get_intent_nonce(env: Env, participant: Address) -> u64 {
+        storage::bump_instance(&env);
+        storage::get_intent_nonce(&env, &participant)
+    }
+
+    /// Pull a participant's outstanding obligation using an allowance they granted this
+    /// contract beforehand, so no signature from them is needed at collection time.
+    /// `caller` must be the split creator or hold the `Keeper` role. Applies the same
+    /// checks as `deposit` and returns the amount collected.
+    pub fn collect_from(
+        env: Env,
+        caller: Address,
+        split_id: u64,
+        participant: Address,
+    ) -> Result<i128, Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
+        let mut split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
+        if caller == split.creator {
+            caller.require_auth();
+        } else {
+            access::require_role(&env, &caller, Role::Keeper)?;
         }
 
         let obligation = split
             .obligations
             .get(participant.clone())
             .ok_or(Error::ParticipantNotOwed)?;
-        if obligation <= 0 {
-            return Err(Error::InvalidAmount);
+        let amount = obligation - split.balances.get(participant.clone()).unwrap_or(0i128);
+        credit_deposit(&env, &mut split, &participant, amount)?;
+
+        let contract = env.current_contract_address();
+        let token_client = token::Client::new(&env, &split.token);
+        if token_client.allowance(&participant, &contract) < amount {
+            return Err(Error::InsufficientAllowance);
         }
+        token_client.transfer_from(&contract, &participant, &contract, &amount);
+
+        storage::set_split(&env, &split);
+        events::emit_deposit(&env, split_id, &participant, amount);
+        Ok(amount)
+    }
+
+    /// Creator-only: propose new obligations for a `Pending` split. `obligations` must sum to
+    /// `total_amount`, which may differ from the current total. Every participant whose
+    /// obligation changes (including added or removed ones) must call `approve_amendment`.
+    pub fn propose_amendment(
+        env: Env,
+        split_id: u64,
+        obligations: Map<Address, i128>,
+        total_amount: i128,
+    ) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
+        amendments::propose(&env, split_id, obligations, total_amount)
+    }
+
+    /// Approve the pending amendment. The final approval applies it atomically, refunding
+    /// balances above the new obligations; returns whether it was applied.
+    pub fn approve_amendment(env: Env, split_id: u64, participant: Address) -> Result<bool, Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
+        amendments::approve(&env, split_id, &participant)
+    }
+
+    pub fn get_amendment(env: Env, split_id: u64) -> Option<Amendment> {
+        storage::bump_instance(&env);
+        storage::get_amendment(&env, split_id)
+    }
 
-        if split.deposited_amount + amount > split.total_amount {
+    /// Hand `amount` of `from`'s obligation to `to` while the split is `Pending`; both must
+    /// authorize. Whatever part of `from`'s deposited balance would exceed their reduced
+    /// obligation moves with it, so the total owed and the total deposited are unchanged.
+    /// `to` must pass the whitelist, and becoming a depositor must fit `max_participants`.
+    /// A pending amendment is discarded, since its approvals were given for the old
+    /// obligations.
+    pub fn transfer_obligation(
+        env: Env,
+        split_id: u64,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        from.require_auth();
+        to.require_auth();
+        access::require_split_open(&env, split_id)?;
+        if amount <= 0 {
             return Err(Error::InvalidAmount);
         }
+        if from == to {
+            return Err(Error::InvalidInput);
+        }
 
-        let previous_balance = split.balances.get(participant.clone()).unwrap_or(0i128);
-        if previous_balance + amount > obligation {
-            return Err(Error::InvalidAmount);
+        let mut split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
+        if !state_machine::accepts_deposits(&split.status) {
+            return Err(Error::SplitNotPending);
+        }
+        if storage::is_whitelist_enabled(&env, split_id)
+            && !storage::is_whitelisted(&env, split_id, &to)
+        {
+            return Err(Error::Unauthorized);
         }
 
-        if !participant_known(&split.participants, &participant) {
-            if split.participants.len() >= split.max_participants {
+        let from_obligation = split
+            .obligations
+            .get(from.clone())
+            .ok_or(Error::ParticipantNotOwed)?;
+        if amount > from_obligation {
+            return Err(Error::InvalidAmount);
+        }
+        let from_remaining = from_obligation - amount;
+        let from_balance = split.balances.get(from.clone()).unwrap_or(0);
+        let moved = (from_balance - from_remaining).max(0);
+
+        if moved > 0 && !participant_known(&split.participants, &to) {
+            // `from` stops being a depositor when their whole balance moves.
+            let frees_slot = moved == from_balance;
+            if !frees_slot && split.participants.len() >= split.max_participants {
                 return Err(Error::ParticipantCapExceeded);
             }
-            split.participants.push_back(participant.clone());
+            split.participants.push_back(to.clone());
+        }
+
+        if from_remaining == 0 {
+            split.obligations.remove(from.clone());
+            storage::unindex_by_participant(&env, &from, split_id);
+        } else {
+            split.obligations.set(from.clone(), from_remaining);
+        }
+        let to_obligation = split.obligations.get(to.clone()).unwrap_or(0);
+        split.obligations.set(to.clone(), to_obligation + amount);
+        storage::index_by_participant(&env, &to, split_id);
+
+        if moved > 0 {
+            if moved == from_balance {
+                split.balances.remove(from.clone());
+                remove_participant(&mut split.participants, &from);
+            } else {
+                split.balances.set(from.clone(), from_balance - moved);
+            }
+            let to_balance = split.balances.get(to.clone()).unwrap_or(0);
+            split.balances.set(to.clone(), to_balance + moved);
         }
 
-        split
-            .balances
-            .set(participant.clone(), previous_balance + amount);
+        if storage::get_amendment(&env, split_id).is_some() {
+            storage::remove_amendment(&env, split_id);
+        }
+        storage::set_split(&env, &split);
+        events::emit_obligation_transferred(&env, split_id, &from, &to, amount, moved);
+        Ok(())
+    }
 
-        let token_address = storage::get_token(&env);
-        let token_client = token::Client::new(&env, &token_address);
-        token_client.transfer(&participant, &env.current_contract_address(), &amount);
+    /// Withdraw part or all of a participant's deposit while the split is still `Pending`.
+    /// A participant whose balance drops to zero is removed from `participants`.
+    pub fn withdraw_deposit(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        participant.require_auth();
+        access::require_split_open(&env, split_id)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
+        if !state_machine::accepts_deposits(&split.status) {
+            return Err(Error::SplitNotPending);
+        }
 
-        split.deposited_amount += amount;
-        if split.deposited_amount == split.total_amount {
-            split.status = SplitStatus::Ready;
+        let balance = split.balances.get(participant.clone()).unwrap_or(0i128);
+        if amount > balance {
+            return Err(Error::InsufficientBalance);
+        }
+        if amount > split.deposited_amount - staking::locked(&env, split_id) {
+            return Err(Error::FundsStaked);
         }
 
+        let remaining = balance - amount;
+        if remaining == 0 {
+            split.balances.remove(participant.clone());
+            remove_participant(&mut split.participants, &participant);
+        } else {
+            split.balances.set(participant.clone(), remaining);
+        }
+        split.deposited_amount -= amount;
+
+        let token_client = token::Client::new(&env, &split.token);
+        token_client.transfer(&env.current_contract_address(), &participant, &amount);
+
         storage::set_split(&env, &split);
-        events::emit_deposit(&env, split_id, &participant, amount);
+        events::emit_withdrawal(&env, split_id, &participant, amount);
         Ok(())
     }
 
-    pub fn add_to_whitelist(env: Env, split_id: u64, address: Address) -> Result<(), Error> {
+    pub fn add_to_whitelist(
+        env: Env,
+        caller: Address,
+        split_id: u64,
+        address: Address,
+    ) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
         let split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
-        split.creator.require_auth();
+        managers::require_permission(&env, &split, &caller, Permission::ManageWhitelist)?;
         storage::add_to_whitelist(&env, split_id, &address);
         Ok(())
     }
 
-    pub fn remove_from_whitelist(env: Env, split_id: u64, address: Address) -> Result<(), Error> {
+    pub fn remove_from_whitelist(
+        env: Env,
+        caller: Address,
+        split_id: u64,
+        address: Address,
+    ) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
         let split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
-        split.creator.require_auth();
+        managers::require_permission(&env, &split, &caller, Permission::ManageWhitelist)?;
         storage::remove_from_whitelist(&env, split_id, &address);
         Ok(())
     }
 
-    pub fn toggle_whitelist(env: Env, split_id: u64, enabled: bool) -> Result<(), Error> {
+    /// Whitelist changes need the creator or a co-manager with `ManageWhitelist`.
+    pub fn toggle_whitelist(
+        env: Env,
+        caller: Address,
+        split_id: u64,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
         let split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
-        split.creator.require_auth();
+        managers::require_permission(&env, &split, &caller, Permission::ManageWhitelist)?;
         storage::set_whitelist_enabled(&env, split_id, enabled);
         Ok(())
     }
 
-    pub fn release_funds(env: Env, split_id: u64) -> Result<(), Error> {
+    /// Release everything still held, including any milestone tranches not yet paid out.
+    /// `caller` must be the creator or a co-manager with `Release`.
+    pub fn release_funds(env: Env, caller: Address, split_id: u64) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
         let mut split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
-        // Only the split creator can finalize settlement.
-        split.creator.require_auth();
-        if split.status != SplitStatus::Ready {
+        managers::require_permission(&env, &split, &caller, Permission::Release)?;
+        if !state_machine::can_release(&split.status) {
             return Err(Error::SplitNotReady);
         }
+        if staking::begin_exit(&env, &mut split, SplitStatus::Released)? {
+            storage::set_split(&env, &split);
+            return Ok(());
+        }
 
-        let total = split.deposited_amount;
-        let fee_amount = fees::collect_fee(&env, total)?;
-        let creator_amount = total - fee_amount;
+        let released_amount = settle(&env, &mut split)?;
+        storage::set_split(&env, &split);
+        events::emit_released(&env, split_id, released_amount);
+        Ok(())
+    }
 
-        let token_address = storage::get_token(&env);
-        let token_client = token::Client::new(&env, &token_address);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &split.creator,
-            &creator_amount,
-        );
+    /// Deposit into several splits in one call. Every item is validated as `deposit` would
+    /// before anything moves; if any fails, nothing is applied and the per-item outcomes
+    /// say which items were rejected and why. At most 20 items, each split at most once.
+    pub fn deposit_many(
+        env: Env,
+        participant: Address,
+        items: Vec<(u64, i128)>,
+    ) -> Result<Vec<BatchOutcome>, Error> {
+        storage::bump_instance(&env);
+        batch::deposit_many(&env, &participant, &items)
+    }
+
+    /// Release several `Ready` or `PartiallyReleased` splits in one call, all or nothing as in
+    /// `deposit_many`. `caller` must be each split's creator or a co-manager with `Release`.
+    pub fn release_many(
+        env: Env,
+        caller: Address,
+        split_ids: Vec<u64>,
+    ) -> Result<Vec<BatchOutcome>, Error> {
+        storage::bump_instance(&env);
+        batch::release_many(&env, &caller, &split_ids)
+    }
 
-        split.status = SplitStatus::Released;
+    /// Pay out the milestone tranche at `index`; `caller` must be the creator or a co-manager
+    /// with `Release`. Tranches are released in order; the split is `PartiallyReleased`
+    /// until the last one is paid.
+    pub fn release_milestone(
+        env: Env,
+        caller: Address,
+        split_id: u64,
+        index: u32,
+    ) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
+        let mut split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
+        managers::require_permission(&env, &split, &caller, Permission::Release)?;
+        if !state_machine::can_release(&split.status) {
+            return Err(Error::SplitNotReady);
+        }
+        if index >= split.milestones.len() {
+            return Err(Error::InvalidMilestones);
+        }
+        if index != split.milestones_released {
+            return Err(Error::MilestoneOutOfOrder);
+        }
+
+        let amount = split.milestones.get(index).unwrap();
+        let net_amount = release_tranche(&env, &mut split, amount)?;
+        split.milestones_released += 1;
+
+        let is_last = split.milestones_released == split.milestones.len();
+        let next = if is_last {
+            SplitStatus::Released
+        } else {
+            SplitStatus::PartiallyReleased
+        };
+        if next != split.status {
+            state_machine::apply(&env, &mut split, next)?;
+        }
         storage::set_split(&env, &split);
-        events::emit_released(&env, split_id, creator_amount);
+        events::emit_milestone_released(&env, split_id, index, net_amount);
+        if is_last {
+            events::emit_released(&env, split_id, net_amount);
+        }
+        Ok(())
+    }
+
+    /// Admin-only: register the contract allowed to put splits on dispute hold.
+    pub fn set_dispute_contract(env: Env, contract: Address) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_admin(&env)?;
+        storage::set_dispute_contract(&env, &contract);
+        events::emit_dispute_contract_updated(&env, &contract);
         Ok(())
     }
 
+    pub fn get_dispute_contract(env: Env) -> Option<Address> {
+        storage::bump_instance(&env);
+        storage::get_dispute_contract(&env)
+    }
+
+    /// Admin-only: register the path-payment contract used by `deposit_with_conversion`.
+    pub fn set_path_payment_contract(env: Env, contract: Address) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_admin(&env)?;
+        storage::set_path_payment_contract(&env, &contract);
+        events::emit_path_payment_contract_updated(&env, &contract);
+        Ok(())
+    }
+
+    pub fn get_path_payment_contract(env: Env) -> Option<Address> {
+        storage::bump_instance(&env);
+        storage::get_path_payment_contract(&env)
+    }
+
+    /// Admin-only: register the staking contract used by yield-mode splits and the token
+    /// it stakes. Only splits escrowing that token can opt in.
+    pub fn set_staking_contract(env: Env, contract: Address, token: Address) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_admin(&env)?;
+        let config = StakingConfig { contract, token };
+        storage::set_staking_config(&env, &config);
+        events::emit_staking_config_updated(&env, &config);
+        Ok(())
+    }
+
+    pub fn get_staking_contract(env: Env) -> Option<StakingConfig> {
+        storage::bump_instance(&env);
+        storage::get_staking_config(&env)
+    }
+
+    /// Stake the funds a yield-mode split holds that are not staked yet. `caller` must be
+    /// the split creator or hold the `Keeper` role. Fails while an amendment is pending,
+    /// since applying it may refund deposits. Returns the amount staked.
+    pub fn stake_idle(env: Env, caller: Address, split_id: u64) -> Result<i128, Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
+        let split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
+        if caller == split.creator {
+            caller.require_auth();
+        } else {
+            access::require_role(&env, &caller, Role::Keeper)?;
+        }
+        staking::stake_idle(&env, &split)
+    }
+
+    /// Pay out the staking rewards a yield-mode split has earned so far, to its depositors
+    /// or its creator per the policy chosen at creation. Returns the amount paid.
+    pub fn claim_staking_rewards(env: Env, split_id: u64) -> Result<i128, Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
+        let split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
+        staking::claim(&env, &split)
+    }
+
+    /// Finish the release, cancellation or expiry of an `Unstaking` split once the
+    /// staking cooldown is over: withdraw its funds, pay out its rewards and settle.
+    /// Returns `false`, after moving the escrow's unstaking batches along, while the
+    /// split's funds are still queued or cooling down. Permissionless, since the outcome
+    /// was authorized when the exit started. Like `release_funds`, a release cannot be
+    /// completed while paused or frozen; refunds always can.
+    pub fn complete_unstake(env: Env, split_id: u64) -> Result<bool, Error> {
+        storage::bump_instance(&env);
+        let mut split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
+        if split.status != SplitStatus::Unstaking {
+            return Err(Error::InvalidInput);
+        }
+        let Some(exit) = staking::finish_exit(&env, &split)? else {
+            return Ok(false);
+        };
+        match exit {
+            SplitStatus::Released => {
+                access::require_split_open(&env, split_id)?;
+                let released_amount = settle(&env, &mut split)?;
+                events::emit_released(&env, split_id, released_amount);
+            }
+            SplitStatus::Expired => {
+                let refunded = refund_expired(&env, &mut split)?;
+                events::emit_expired(&env, split_id, refunded);
+            }
+            _ => {
+                refund_remaining(&env, &mut split)?;
+                events::emit_cancelled(&env, split_id);
+            }
+        }
+        storage::set_split(&env, &split);
+        Ok(true)
+    }
+
+    pub fn get_yield_position(env: Env, split_id: u64) -> Option<YieldPosition> {
+        storage::bump_instance(&env);
+        storage::get_yield_position(&env, split_id)
+    }
+
+    /// Dispute contract only: move a live split to `Disputed` for a dispute raised by
+    /// `raiser`, who must be the creator or hold an obligation in the split. Release and
+    /// cancellation are then blocked until the dispute contract calls `reverse_split` or
+    /// `lift_dispute_hold`.
+    pub fn hold_for_dispute(env: Env, raiser: Address, split_id: u64) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        disputes::hold(&env, split_id, &raiser)
+    }
+
+    /// Dispute contract only: return a `Disputed` split to its pre-dispute status, as when
+    /// a dispute is dismissed. Returns the restored status.
+    pub fn lift_dispute_hold(env: Env, split_id: u64) -> Result<SplitStatus, Error> {
+        storage::bump_instance(&env);
+        disputes::lift(&env, split_id)
+    }
+
+    /// Admin only: lift a dispute hold that is at least 30 days old, so a dispute that is
+    /// never resolved cannot lock the split for good. Returns the restored status.
+    pub fn clear_stale_dispute_hold(env: Env, split_id: u64) -> Result<SplitStatus, Error> {
+        storage::bump_instance(&env);
+        disputes::clear_stale(&env, split_id)
+    }
+
+    pub fn get_dispute_hold(env: Env, split_id: u64) -> Option<DisputeHold> {
+        storage::bump_instance(&env);
+        storage::get_dispute_hold(&env, split_id)
+    }
+
     /// Alias for cancellation that matches the dispute contract's "reverse_split" concept.
-    pub fn reverse_split(env: Env, split_id: u64) -> Result<(), Error> {
-        Self::cancel_split(env, split_id)
+    pub fn reverse_split(env: Env, caller: Address, split_id: u64) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        Self::cancel_split(env, caller, split_id)
+    }
+
+    /// Creator-only: grant `manager` exactly `permissions` on the split, replacing what
+    /// it held before. An empty list removes the manager. At most 10 managers per split.
+    pub fn set_manager(
+        env: Env,
+        split_id: u64,
+        manager: Address,
+        permissions: Vec<Permission>,
+    ) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
+        managers::set_manager(&env, split_id, &manager, permissions)
+    }
+
+    pub fn get_managers(env: Env, split_id: u64) -> Map<Address, Vec<Permission>> {
+        storage::bump_instance(&env);
+        storage::get_managers(&env, split_id)
+    }
+
+    /// Whether `address` may perform `permission` on the split. The creator holds every
+    /// permission.
+    pub fn has_permission(
+        env: Env,
+        split_id: u64,
+        address: Address,
+        permission: Permission,
+    ) -> Result<bool, Error> {
+        storage::bump_instance(&env);
+        let split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
+        Ok(managers::has_permission(&env, &split, &address, permission))
+    }
+
+    /// First step of a creator handover: the creator nominates `new_creator`.
+    pub fn propose_creator(env: Env, split_id: u64, new_creator: Address) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
+        managers::propose_creator(&env, split_id, &new_creator)
+    }
+
+    /// Second step: the nominee accepts and becomes the split's creator.
+    pub fn accept_creator(env: Env, split_id: u64) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
+        managers::accept_creator(&env, split_id)
+    }
+
+    pub fn get_pending_creator(env: Env, split_id: u64) -> Option<Address> {
+        storage::bump_instance(&env);
+        storage::get_pending_creator(&env, split_id)
+    }
+
+    /// Admin or fee manager: set the flat fee rate used when no schedule tiers exist.
+    pub fn set_fee(env: Env, caller: Address, fee_bps: u32) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        fees::set_fee(&env, &caller, fee_bps)
+    }
+
+    /// Admin or fee manager: configure volume tiers and absolute fee bounds. See
+    /// `fees::set_fee_schedule`.
+    pub fn set_fee_schedule(env: Env, caller: Address, schedule: FeeSchedule) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        fees::set_fee_schedule(&env, &caller, &schedule)
+    }
+
+    pub fn get_fee_schedule(env: Env) -> Option<FeeSchedule> {
+        storage::bump_instance(&env);
+        storage::get_fee_schedule(&env)
+    }
+
+    /// Admin or fee manager: give `creator` a fixed rate that bypasses the schedule; `None`
+    /// clears it.
+    pub fn set_creator_fee(
+        env: Env,
+        caller: Address,
+        creator: Address,
+        fee_bps: Option<u32>,
+    ) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        fees::set_creator_fee(&env, &caller, &creator, fee_bps)
+    }
+
+    /// Admin or fee manager: set where fees are sent.
+    pub fn set_treasury(env: Env, caller: Address, address: Address) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        fees::set_treasury(&env, &caller, &address)
+    }
+
+    /// Admin or pauser: engage or lift the global circuit breaker. While paused, every
+    /// split-changing entry point fails with `Paused` except the refund paths
+    /// (`cancel_split`, `reverse_split`, `expire_split`).
+    pub fn set_paused(env: Env, caller: Address, paused: bool) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::set_paused(&env, &caller, paused)
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        storage::bump_instance(&env);
+        storage::is_paused(&env)
+    }
+
+    /// Admin or pauser: freeze or unfreeze a single split. Refund paths stay open.
+    pub fn set_split_frozen(
+        env: Env,
+        caller: Address,
+        split_id: u64,
+        frozen: bool,
+    ) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::set_frozen(&env, &caller, split_id, frozen)
+    }
+
+    pub fn is_split_frozen(env: Env, split_id: u64) -> bool {
+        storage::bump_instance(&env);
+        storage::is_frozen(&env, split_id)
     }
 
-    pub fn set_fee(env: Env, fee_bps: u32) -> Result<(), Error> {
-        fees::set_fee(&env, fee_bps)
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        storage::bump_instance(&env);
+        if !storage::has_admin(&env) {
+            return Err(Error::NotInitialized);
+        }
+        Ok(storage::get_admin(&env))
+    }
+
+    /// Admin-only: nominate `new_admin`. Takes effect once they call `accept_admin`.
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::propose_admin(&env, &new_admin)
+    }
+
+    /// Complete a pending admin transfer; must be authorized by the nominee.
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::accept_admin(&env)
     }
 
-    pub fn set_treasury(env: Env, address: Address) -> Result<(), Error> {
-        fees::set_treasury(&env, &address)
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        storage::bump_instance(&env);
+        storage::get_pending_admin(&env)
+    }
+
+    /// Admin-only: grant `role` to `account`.
+    pub fn grant_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::grant_role(&env, role, &account)
+    }
+
+    /// Admin-only: revoke `role` from `account`.
+    pub fn revoke_role(env: Env, role: Role, account: Address) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::revoke_role(&env, role, &account)
+    }
+
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        storage::bump_instance(&env);
+        storage::has_role(&env, &role, &account)
+    }
+
+    /// Per-participant obligations and paid amounts, fees, payouts and status history of a
+    /// split, with a sha256 commitment over them. Most useful once the split is settled.
+    pub fn get_settlement_receipt(env: Env, split_id: u64) -> Result<SettlementReceipt, Error> {
+        storage::bump_instance(&env);
+        let split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
+        Ok(receipts::build(&env, &split))
+    }
+
+    /// Admin-only: update participant, note and metadata limits for new splits and edits.
+    /// Each is capped so that a split at every ceiling still fits one ledger entry.
+    pub fn set_limits(env: Env, limits: Limits) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        limits::set(&env, &limits)
+    }
+
+    pub fn get_limits(env: Env) -> Limits {
+        storage::bump_instance(&env);
+        limits::get(&env)
+    }
+
+    /// Admin-only: set how far storage TTLs are extended. `threshold` must be below
+    /// `extend_to`, which may not exceed the network's maximum TTL.
+    pub fn set_ttl_policy(env: Env, policy: TtlPolicy) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        access::require_admin(&env)?;
+        if policy.threshold >= policy.extend_to || policy.extend_to > env.storage().max_ttl() {
+            return Err(Error::InvalidInput);
+        }
+        storage::set_ttl_policy(&env, &policy);
+        events::emit_ttl_policy_updated(&env, &policy);
+        Ok(())
+    }
+
+    pub fn get_ttl_policy(env: Env) -> TtlPolicy {
+        storage::bump_instance(&env);
+        storage::get_ttl_policy(&env)
+    }
+
+    /// Extend a split and its per-split entries to the full policy TTL. Anyone may call this
+    /// (and pay for it) to keep a long-lived split from being archived. Entries that are
+    /// already archived must first be restored with a `RestoreFootprint` operation.
+    pub fn extend_split_ttl(env: Env, split_id: u64) -> Result<(), Error> {
+        storage::bump_instance(&env);
+        if !storage::extend_split_ttl(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        Ok(())
     }
 
     /// Returns escrow state including `max_participants` and `participants` (count =
     /// `participants.len()`).
     pub fn get_escrow(env: Env, split_id: u64) -> Result<Split, Error> {
+        storage::bump_instance(&env);
         storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)
     }
 
+    /// Page through split ids created by `creator`, in no particular order. Pass `cursor = 0`
+    /// for the first page and the returned `next` afterwards; `limit` is capped at 50. If
+    /// `status` is set, only splits currently in that status are returned.
+    pub fn get_splits_by_creator(
+        env: Env,
+        creator: Address,
+        status: Option<SplitStatus>,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<SplitIdPage, Error> {
+        storage::bump_instance(&env);
+        queries::by_creator(&env, &creator, &status, cursor, limit)
+    }
+
+    /// Page through split ids in which `participant` holds an obligation. Same paging rules
+    /// as `get_splits_by_creator`.
+    pub fn get_splits_by_participant(
+        env: Env,
+        participant: Address,
+        status: Option<SplitStatus>,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<SplitIdPage, Error> {
+        storage::bump_instance(&env);
+        queries::by_participant(&env, &participant, &status, cursor, limit)
+    }
+
+    /// Page through split ids currently in `status`. Same paging rules as
+    /// `get_splits_by_creator`.
+    pub fn get_splits_by_status(
+        env: Env,
+        status: SplitStatus,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<SplitIdPage, Error> {
+        storage::bump_instance(&env);
+        queries::by_status(&env, &status, cursor, limit)
+    }
+
     /// View helper for dispute-resolution auth checks.
     pub fn get_creator(env: Env, split_id: u64) -> Result<Address, Error> {
+        storage::bump_instance(&env);
         let split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
         Ok(split.creator)
     }
 
     pub fn get_metadata(env: Env, split_id: u64) -> Result<Map<String, String>, Error> {
+        storage::bump_instance(&env);
         let split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
         Ok(split.metadata)
     }
@@ -379,11 +1525,13 @@ impl SplitEscrowContract {
         split_id: u64,
         metadata: Map<String, String>,
     ) -> Result<(), Error> {
-        validate_metadata(&metadata)?;
+        storage::bump_instance(&env);
+        access::require_split_open(&env, split_id)?;
+        validate_metadata(&env, &metadata)?;
 
         let mut split = storage::get_split(&env, split_id).ok_or(Error::SplitNotFound)?;
         split.creator.require_auth();
-        if !is_active(&split.status) {
+        if !state_machine::is_active(&split.status) {
             return Err(Error::SplitNotActive);
         }
 
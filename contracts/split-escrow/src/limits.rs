@@ -0,0 +1,49 @@
+use soroban_sdk::Env;
+
+use crate::{access, errors::Error, events, storage, types::Limits};
+
+/// Values used until the admin calls `set_limits`.
+const DEFAULT_MAX_PARTICIPANTS: u32 = 50;
+const MAX_NOTE_LEN: u32 = 128;
+const MAX_METADATA_ENTRIES: u32 = 32;
+const MAX_METADATA_STRING_LEN: u32 = 128;
+
+/// Ceilings for admin-set limits. A split is a single contract data entry, capped at
+/// 64 KiB, and must fit with every limit maxed out at once: each participant costs
+/// about 160 bytes across `participants`, `obligations` and `balances`, and each
+/// metadata entry about 530 bytes, some 31 KiB in all. Participants are held lower
+/// than size alone allows because creating a split indexes every obligation and
+/// cancelling refunds every depositor, each within one transaction's CPU budget.
+pub const MAX_PARTICIPANTS_BOUND: u32 = 80;
+pub const MAX_NOTE_LEN_BOUND: u32 = 1_024;
+pub const MAX_METADATA_ENTRIES_BOUND: u32 = 32;
+pub const MAX_METADATA_STRING_LEN_BOUND: u32 = 256;
+
+pub fn get(env: &Env) -> Limits {
+    storage::get_limits(env).unwrap_or(Limits {
+        max_participants: DEFAULT_MAX_PARTICIPANTS,
+        max_note_len: MAX_NOTE_LEN,
+        max_metadata_entries: MAX_METADATA_ENTRIES,
+        max_metadata_string_len: MAX_METADATA_STRING_LEN,
+    })
+}
+
+/// Admin-only. Every limit must be positive and within its sanity bound.
+/// Existing splits keep whatever they were created with.
+pub fn set(env: &Env, limits: &Limits) -> Result<(), Error> {
+    access::require_admin(env)?;
+    let within = |value: u32, bound: u32| value > 0 && value <= bound;
+    if !within(limits.max_participants, MAX_PARTICIPANTS_BOUND)
+        || !within(limits.max_note_len, MAX_NOTE_LEN_BOUND)
+        || !within(limits.max_metadata_entries, MAX_METADATA_ENTRIES_BOUND)
+        || !within(
+            limits.max_metadata_string_len,
+            MAX_METADATA_STRING_LEN_BOUND,
+        )
+    {
+        return Err(Error::InvalidInput);
+    }
+    storage::set_limits(env, limits);
+    events::emit_limits_updated(env, limits);
+    Ok(())
+}
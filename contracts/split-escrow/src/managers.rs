@@ -0,0 +1,99 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{
+    errors::Error,
+    events, storage,
+    types::{Permission, Split},
+};
+
+/// Most co-managers a split can have at once.
+pub const MAX_MANAGERS: u32 = 10;
+
+/// Whether `address` may perform `permission` on `split`: the creator always can.
+pub fn has_permission(env: &Env, split: &Split, address: &Address, permission: Permission) -> bool {
+    if *address == split.creator {
+        return true;
+    }
+    storage::get_managers(env, split.split_id)
+        .get(address.clone())
+        .map(|granted| granted.contains(permission))
+        .unwrap_or(false)
+}
+
+/// Require `caller`'s authorization and that it may perform `permission` on `split`.
+pub fn require_permission(
+    env: &Env,
+    split: &Split,
+    caller: &Address,
+    permission: Permission,
+) -> Result<(), Error> {
+    caller.require_auth();
+    if has_permission(env, split, caller, permission) {
+        return Ok(());
+    }
+    Err(Error::Unauthorized)
+}
+
+/// Creator-only: replace `manager`'s permissions on `split_id`. An empty list removes
+/// the manager.
+pub fn set_manager(
+    env: &Env,
+    split_id: u64,
+    manager: &Address,
+    permissions: Vec<Permission>,
+) -> Result<(), Error> {
+    let split = storage::get_split(env, split_id).ok_or(Error::SplitNotFound)?;
+    split.creator.require_auth();
+    if *manager == split.creator {
+        return Err(Error::InvalidInput);
+    }
+
+    let mut managers = storage::get_managers(env, split_id);
+    if permissions.is_empty() {
+        managers.remove(manager.clone());
+    } else {
+        if !managers.contains_key(manager.clone()) && managers.len() >= MAX_MANAGERS {
+            return Err(Error::InvalidInput);
+        }
+        managers.set(manager.clone(), permissions.clone());
+    }
+    storage::set_managers(env, split_id, &managers);
+    events::emit_manager_updated(env, split_id, manager, &permissions);
+    Ok(())
+}
+
+/// First step of a creator handover: the creator nominates `new_creator`. Proposing
+/// again replaces the pending nominee.
+pub fn propose_creator(env: &Env, split_id: u64, new_creator: &Address) -> Result<(), Error> {
+    let split = storage::get_split(env, split_id).ok_or(Error::SplitNotFound)?;
+    split.creator.require_auth();
+    if *new_creator == split.creator {
+        return Err(Error::InvalidInput);
+    }
+    storage::set_pending_creator(env, split_id, new_creator);
+    events::emit_creator_proposed(env, split_id, &split.creator, new_creator);
+    Ok(())
+}
+
+/// Second step: the nominee accepts and becomes the creator. A nominee who was a
+/// co-manager stops being one, as the creator holds every permission.
+pub fn accept_creator(env: &Env, split_id: u64) -> Result<(), Error> {
+    let new_creator = storage::get_pending_creator(env, split_id).ok_or(Error::NoPendingCreator)?;
+    new_creator.require_auth();
+    let mut split = storage::get_split(env, split_id).ok_or(Error::SplitNotFound)?;
+
+    let previous = split.creator.clone();
+    storage::unindex_by_creator(env, &previous, split_id);
+    storage::index_by_creator(env, &new_creator, split_id);
+    split.creator = new_creator.clone();
+    storage::set_split(env, &split);
+
+    let mut managers = storage::get_managers(env, split_id);
+    if managers.contains_key(new_creator.clone()) {
+        managers.remove(new_creator.clone());
+        storage::set_managers(env, split_id, &managers);
+    }
+    storage::remove_pending_creator(env, split_id);
+    events::emit_creator_transferred(env, split_id, &previous, &new_creator);
+    Ok(())
+}
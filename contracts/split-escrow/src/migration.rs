@@ -0,0 +1,139 @@
+/// Storage schema migrations for split-escrow.
+///
+/// A WASM upgrade swaps code but leaves persistent entries untouched, so any
+/// `Split` written under an older layout can no longer be decoded once the
+/// struct gains fields.  `upgrade` records the schema the stored data is in,
+/// and `migrate` walks split ids in bounded batches, rewriting each record in
+/// the current layout.  Records already in the current layout are skipped, so
+/// re-running a batch is harmless.
+///
+/// ## Schema versions
+///
+/// * `1` — original layout: one contract-wide `DataKey::Token`, no deadline,
+///   payout table, milestones or secondary indexes.
+/// * `2` — current `Split` layout.
+use soroban_sdk::{contracttype, Address, Env, FromVal, Map, String, Symbol, Val, Vec};
+
+use crate::{
+    errors::Error,
+    events,
+    storage::{self, DataKey},
+    types::{Split, SplitStatus},
+};
+
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Largest number of split ids a single `migrate` call will visit.
+pub const MAX_MIGRATION_BATCH: u32 = 50;
+
+/// `Split` as stored under schema version 1.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SplitV1 {
+    pub split_id: u64,
+    pub creator: Address,
+    pub description: String,
+    pub metadata: Map<String, String>,
+    pub total_amount: i128,
+    pub deposited_amount: i128,
+    pub status: SplitStatus,
+    pub max_participants: u32,
+    pub participants: Vec<Address>,
+    pub balances: Map<Address, i128>,
+    pub obligations: Map<Address, i128>,
+    pub note: String,
+}
+
+fn upgrade_v1(env: &Env, legacy: SplitV1, token: &Address) -> Split {
+    let released_amount = if legacy.status == SplitStatus::Released {
+        legacy.deposited_amount
+    } else {
+        0
+    };
+    Split {
+        split_id: legacy.split_id,
+        creator: legacy.creator,
+        description: legacy.description,
+        token: token.clone(),
+        metadata: legacy.metadata,
+        total_amount: legacy.total_amount,
+        deposited_amount: legacy.deposited_amount,
+        status: legacy.status,
+        max_participants: legacy.max_participants,
+        participants: legacy.participants,
+        balances: legacy.balances,
+        obligations: legacy.obligations,
+        note: legacy.note,
+        deadline: None,
+        payouts: Vec::new(env),
+        milestones: Vec::new(env),
+        milestones_released: 0,
+        released_amount,
+    }
+}
+
+/// Rewrite one stored split in the current layout. Returns `false` when the
+/// id has no record or the record is already current.
+///
+/// Decoding a struct with the wrong field set traps the host, so the layout
+/// is detected from the raw field map before choosing a type to decode into.
+fn migrate_split(env: &Env, split_id: u64, from_version: u32) -> bool {
+    let raw: Option<Map<Symbol, Val>> = env.storage().persistent().get(&DataKey::Split(split_id));
+    let Some(fields) = raw else {
+        return false;
+    };
+
+    let split = match from_version {
+        1 => {
+            if fields.contains_key(Symbol::new(env, "token")) {
+                return false;
+            }
+            let legacy = SplitV1::from_val(env, &fields.to_val());
+            let token = storage::get_legacy_token(env).unwrap();
+            upgrade_v1(env, legacy, &token)
+        }
+        _ => return false,
+    };
+
+    storage::set_split(env, &split);
+    storage::index_by_creator(env, &split.creator, split_id);
+    for participant in split.obligations.keys().iter() {
+        storage::index_by_participant(env, &participant, split_id);
+    }
+    storage::index_by_status(env, &split.status, split_id);
+    true
+}
+
+/// Migrate up to `batch_size` split ids starting at the stored cursor.
+/// Returns `true` once every split is in the current layout.
+pub fn migrate(env: &Env, batch_size: u32) -> Result<bool, Error> {
+    let from_version = storage::get_schema_version(env);
+    if from_version == CURRENT_SCHEMA_VERSION {
+        return Ok(true);
+    }
+    if batch_size == 0 || batch_size > MAX_MIGRATION_BATCH {
+        return Err(Error::InvalidInput);
+    }
+
+    let end = storage::get_next_split_id(env);
+    let mut cursor = storage::get_migration_cursor(env);
+    let mut migrated = 0u32;
+    let mut visited = 0u32;
+    while cursor < end && visited < batch_size {
+        if migrate_split(env, cursor, from_version) {
+            migrated += 1;
+        }
+        cursor += 1;
+        visited += 1;
+    }
+
+    let done = cursor >= end;
+    if done {
+        storage::set_schema_version(env, CURRENT_SCHEMA_VERSION);
+        storage::clear_migration_cursor(env);
+    } else {
+        storage::set_migration_cursor(env, cursor);
+    }
+    events::emit_migrated(env, from_version, cursor, migrated);
+    Ok(done)
+}
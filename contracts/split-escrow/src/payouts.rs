@@ -0,0 +1,117 @@
+use soroban_sdk::{token, Address, Env, Map, Vec};
+
+use crate::{
+    errors::Error,
+    events,
+    fees::MAX_BPS,
+    types::{Payout, PayoutShare, Split},
+};
+
+const MAX_PAYOUT_RECIPIENTS: u32 = 10;
+
+/// Validate a payout table against the split total.
+///
+/// An empty table means "pay the creator". Otherwise every entry must use the
+/// same share kind: basis points summing to exactly `MAX_BPS`, or fixed amounts
+/// summing to exactly `total_amount`. Recipients must be distinct.
+pub fn validate(payouts: &Vec<Payout>, total_amount: i128) -> Result<(), Error> {
+    if payouts.is_empty() {
+        return Ok(());
+    }
+    if payouts.len() > MAX_PAYOUT_RECIPIENTS {
+        return Err(Error::InvalidPayouts);
+    }
+
+    let use_bps = matches!(payouts.get(0).unwrap().share, PayoutShare::Bps(_));
+    let mut sum = 0i128;
+    let mut i = 0u32;
+    while i < payouts.len() {
+        let payout = payouts.get(i).unwrap();
+        let weight = match payout.share {
+            PayoutShare::Bps(bps) if use_bps => bps as i128,
+            PayoutShare::Fixed(amount) if !use_bps => amount,
+            _ => return Err(Error::InvalidPayouts),
+        };
+        if weight <= 0 {
+            return Err(Error::InvalidPayouts);
+        }
+        let mut j = 0u32;
+        while j < i {
+            if payouts.get(j).unwrap().recipient == payout.recipient {
+                return Err(Error::InvalidPayouts);
+            }
+            j += 1;
+        }
+        sum += weight;
+        i += 1;
+    }
+
+    let expected = if use_bps {
+        MAX_BPS as i128
+    } else {
+        total_amount
+    };
+    if sum != expected {
+        return Err(Error::InvalidPayouts);
+    }
+    Ok(())
+}
+
+fn weight(share: &PayoutShare) -> i128 {
+    match share {
+        PayoutShare::Bps(bps) => *bps as i128,
+        PayoutShare::Fixed(amount) => *amount,
+    }
+}
+
+/// Transfer `net_amount` of the split's token out of the contract according to
+/// its payout table, emitting one event per transfer.
+///
+/// Each recipient receives `net_amount * weight / total_weight`, rounded down;
+/// the rounding remainder goes to the first recipient. Fixed amounts are
+/// treated as weights so fees are borne pro rata. With an empty table the
+/// whole amount goes to the creator. Returns the amount sent to each recipient.
+pub fn distribute(env: &Env, split: &Split, net_amount: i128) -> Map<Address, i128> {
+    let token_client = token::Client::new(env, &split.token);
+    let contract = env.current_contract_address();
+    let mut paid = Map::new(env);
+
+    if split.payouts.is_empty() {
+        token_client.transfer(&contract, &split.creator, &net_amount);
+        events::emit_payout(env, split.split_id, &split.creator, net_amount);
+        paid.set(split.creator.clone(), net_amount);
+        return paid;
+    }
+
+    let mut total_weight = 0i128;
+    let mut i = 0u32;
+    while i < split.payouts.len() {
+        total_weight += weight(&split.payouts.get(i).unwrap().share);
+        i += 1;
+    }
+
+    let mut amounts: Vec<i128> = Vec::new(env);
+    let mut assigned = 0i128;
+    i = 0;
+    while i < split.payouts.len() {
+        let share = net_amount * weight(&split.payouts.get(i).unwrap().share) / total_weight;
+        amounts.push_back(share);
+        assigned += share;
+        i += 1;
+    }
+    let first = amounts.get(0).unwrap();
+    amounts.set(0, first + (net_amount - assigned));
+
+    i = 0;
+    while i < split.payouts.len() {
+        let recipient = split.payouts.get(i).unwrap().recipient;
+        let amount = amounts.get(i).unwrap();
+        if amount > 0 {
+            token_client.transfer(&contract, &recipient, &amount);
+            events::emit_payout(env, split.split_id, &recipient, amount);
+            paid.set(recipient, amount);
+        }
+        i += 1;
+    }
+    paid
+}
@@ -0,0 +1,89 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{
+    errors::Error,
+    storage::{self, IndexKey},
+    types::{SplitIdPage, SplitStatus},
+};
+
+/// Largest page any index query returns.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Scan up to `limit` index positions starting at `cursor`, optionally keeping only
+/// splits currently in `status`. A filtered page may hold fewer than `limit` ids, or
+/// none, while `next` is still set.
+///
+/// Pass `cursor = 0` for the first page and the returned `next` afterwards.
+fn page(
+    env: &Env,
+    key: &IndexKey,
+    status: &Option<SplitStatus>,
+    cursor: u32,
+    limit: u32,
+) -> Result<SplitIdPage, Error> {
+    if limit == 0 {
+        return Err(Error::InvalidInput);
+    }
+    let limit = limit.min(MAX_PAGE_SIZE);
+    let len = storage::index_len(env, key);
+    let start = cursor.min(len);
+    let end = start.saturating_add(limit).min(len);
+
+    let mut split_ids = Vec::new(env);
+    for split_id in storage::index_range(env, key, start, end).iter() {
+        let keep = match status {
+            Some(wanted) => storage::get_split(env, split_id)
+                .map(|split| split.status == *wanted)
+                .unwrap_or(false),
+            None => true,
+        };
+        if keep {
+            split_ids.push_back(split_id);
+        }
+    }
+    Ok(SplitIdPage {
+        split_ids,
+        next: if end < len { Some(end) } else { None },
+    })
+}
+
+pub fn by_creator(
+    env: &Env,
+    creator: &Address,
+    status: &Option<SplitStatus>,
+    cursor: u32,
+    limit: u32,
+) -> Result<SplitIdPage, Error> {
+    page(
+        env,
+        &IndexKey::Creator(creator.clone()),
+        status,
+        cursor,
+        limit,
+    )
+}
+
+pub fn by_participant(
+    env: &Env,
+    participant: &Address,
+    status: &Option<SplitStatus>,
+    cursor: u32,
+    limit: u32,
+) -> Result<SplitIdPage, Error> {
+    page(
+        env,
+        &IndexKey::Participant(participant.clone()),
+        status,
+        cursor,
+        limit,
+    )
+}
+
+pub fn by_status(
+    env: &Env,
+    status: &SplitStatus,
+    cursor: u32,
+    limit: u32,
+) -> Result<SplitIdPage, Error> {
+    page(env, &IndexKey::Status(status.clone()), &None, cursor, limit)
+}
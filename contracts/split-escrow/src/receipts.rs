@@ -0,0 +1,103 @@
+use soroban_sdk::{xdr::ToXdr, Address, Env, Map, Vec};
+
+use crate::{
+    storage,
+    types::{
+        ReceiptLine, SettlementLog, SettlementReceipt, SettlementSummary, Split, SplitStatus,
+        StatusStamp,
+    },
+};
+
+fn load(env: &Env, split_id: u64) -> SettlementLog {
+    storage::get_settlement_log(env, split_id).unwrap_or(SettlementLog {
+        fee: 0,
+        treasury: None,
+        payouts: Map::new(env),
+        deposits: Map::new(env),
+        refunds: Map::new(env),
+        history: Vec::new(env),
+    })
+}
+
+/// Stamp the current ledger onto the split's status history.
+pub fn record_status(env: &Env, split_id: u64, status: &SplitStatus) {
+    let mut log = load(env, split_id);
+    log.history.push_back(StatusStamp {
+        status: status.clone(),
+        ledger: env.ledger().sequence(),
+        timestamp: env.ledger().timestamp(),
+    });
+    storage::set_settlement_log(env, split_id, &log);
+}
+
+/// Add one release's fee and per-recipient amounts to the log.
+pub fn record_release(
+    env: &Env,
+    split_id: u64,
+    fee: i128,
+    treasury: &Address,
+    paid: &Map<Address, i128>,
+) {
+    let mut log = load(env, split_id);
+    log.fee += fee;
+    log.treasury = Some(treasury.clone());
+    for (recipient, amount) in paid.iter() {
+        let previous = log.payouts.get(recipient.clone()).unwrap_or(0);
+        log.payouts.set(recipient, previous + amount);
+    }
+    storage::set_settlement_log(env, split_id, &log);
+}
+
+/// Record what a cancellation or expiry refunded, before it zeroes the balances in
+/// `deposits`.
+pub fn record_refunds(
+    env: &Env,
+    split_id: u64,
+    deposits: &Map<Address, i128>,
+    refunds: &Map<Address, i128>,
+) {
+    let mut log = load(env, split_id);
+    log.deposits = deposits.clone();
+    log.refunds = refunds.clone();
+    storage::set_settlement_log(env, split_id, &log);
+}
+
+/// Build the receipt for `split` from its current state and settlement log.
+/// Splits migrated from schema v1 have no history before the migration.
+pub fn build(env: &Env, split: &Split) -> SettlementReceipt {
+    let log = load(env, split.split_id);
+
+    let mut lines = Vec::new(env);
+    for (participant, obligation) in split.obligations.iter() {
+        // A refunded split's balances are zero; what was paid is in the log.
+        let paid = log
+            .deposits
+            .get(participant.clone())
+            .or_else(|| split.balances.get(participant.clone()))
+            .unwrap_or(0);
+        let refunded = log.refunds.get(participant.clone()).unwrap_or(0);
+        lines.push_back(ReceiptLine {
+            participant,
+            obligation,
+            paid,
+            refunded,
+        });
+    }
+
+    let summary = SettlementSummary {
+        split_id: split.split_id,
+        token: split.token.clone(),
+        status: split.status.clone(),
+        total_amount: split.total_amount,
+        lines,
+        fee: log.fee,
+        treasury: log.treasury,
+        payouts: log.payouts,
+        history: log.history,
+    };
+    let commitment = env.crypto().sha256(&summary.clone().to_xdr(env)).into();
+    SettlementReceipt {
+        summary,
+        commitment,
+    }
+}
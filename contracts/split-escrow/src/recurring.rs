@@ -0,0 +1,133 @@
+use soroban_sdk::{Address, Env, Map, String, Vec};
+
+use crate::{
+    access,
+    errors::Error,
+    events,
+    interface::CreateEscrowParams,
+    storage,
+    types::{CycleLink, RecurringSplit, YieldPolicy},
+};
+
+/// Upper bound on `cycles` so a schedule's history stays a bounded entry.
+pub const MAX_CYCLES: u32 = 120;
+
+/// Store a new schedule. The first cycle can be rolled immediately.
+#[allow(clippy::too_many_arguments)]
+pub fn create(
+    env: &Env,
+    creator: Address,
+    description: String,
+    token: Address,
+    total_amount: i128,
+    obligations: Map<Address, i128>,
+    period: u64,
+    cycles: u32,
+) -> Result<u64, Error> {
+    if period == 0 || cycles == 0 || cycles > MAX_CYCLES {
+        return Err(Error::InvalidInput);
+    }
+    if total_amount <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+    if !storage::is_token_allowed(env, &token) {
+        return Err(Error::TokenNotAllowed);
+    }
+    crate::validate_obligations(&obligations, total_amount)?;
+
+    let recurring_id = storage::get_next_recurring_id(env);
+    storage::bump_next_recurring_id(env);
+
+    let recurring = RecurringSplit {
+        recurring_id,
+        creator,
+        description,
+        token,
+        total_amount,
+        obligations,
+        period,
+        cycles,
+        next_cycle_at: env.ledger().timestamp(),
+        paused: false,
+        history: Vec::new(env),
+    };
+    storage::set_recurring(env, &recurring);
+    events::emit_recurring_created(env, &recurring);
+    Ok(recurring_id)
+}
+
+/// Instantiate the next cycle's split once its start time has been reached.
+///
+/// Cycles missed while nobody called this can be rolled back to back; each one
+/// advances the schedule by exactly one period.
+pub fn roll(env: &Env, recurring_id: u64) -> Result<u64, Error> {
+    access::require_not_paused(env)?;
+    let mut recurring =
+        storage::get_recurring(env, recurring_id).ok_or(Error::RecurringNotFound)?;
+    if recurring.paused {
+        return Err(Error::Paused);
+    }
+    if recurring.history.len() >= recurring.cycles {
+        return Err(Error::CyclesExhausted);
+    }
+    if env.ledger().timestamp() < recurring.next_cycle_at {
+        return Err(Error::CycleNotDue);
+    }
+
+    let split_id = crate::open_split(
+        env,
+        CreateEscrowParams {
+            creator: recurring.creator.clone(),
+            description: recurring.description.clone(),
+            total_amount: recurring.total_amount,
+            token: recurring.token.clone(),
+            obligations: recurring.obligations.clone(),
+            payouts: Vec::new(env),
+            milestones: Vec::new(env),
+            max_participants: None,
+            whitelist_enabled: false,
+            metadata: Map::new(env),
+            note: None,
+            deadline: None,
+            yield_policy: YieldPolicy::Disabled,
+        },
+    )?;
+
+    let cycle = recurring.history.len();
+    let previous = recurring.history.last();
+    storage::set_cycle_link(
+        env,
+        split_id,
+        &CycleLink {
+            recurring_id,
+            cycle,
+            previous,
+        },
+    );
+
+    recurring.history.push_back(split_id);
+    recurring.next_cycle_at += recurring.period;
+    storage::set_recurring(env, &recurring);
+    events::emit_cycle_rolled(env, recurring_id, cycle, split_id, previous);
+    Ok(split_id)
+}
+
+/// Creator-only. Resuming never back-fills: if the schedule fell behind while
+/// paused, the next cycle becomes due immediately and later ones follow one
+/// period apart.
+pub fn set_paused(env: &Env, recurring_id: u64, paused: bool) -> Result<(), Error> {
+    let mut recurring =
+        storage::get_recurring(env, recurring_id).ok_or(Error::RecurringNotFound)?;
+    recurring.creator.require_auth();
+
+    if recurring.paused && !paused {
+        let now = env.ledger().timestamp();
+        if recurring.next_cycle_at < now {
+            recurring.next_cycle_at = now;
+        }
+    }
+    recurring.paused = paused;
+    storage::set_recurring(env, &recurring);
+    events::emit_recurring_paused(env, recurring_id, paused);
+    Ok(())
+}
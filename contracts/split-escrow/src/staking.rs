@@ -0,0 +1,260 @@
+//! Opt-in yield mode. The escrow holds one position in the registered staking
+//! contract on behalf of every yield-mode split and tracks each split's share of it
+//! with its own reward index, the same way the staking contract tracks its stakers.
+//!
+//! The staking contract keeps a single cooldown per staker and restarts it on every
+//! unstake, so exits are unstaked in batches: while one batch cools down, later exits
+//! queue behind it and are unstaked together once it has been withdrawn. An exit thus
+//! waits at most two cooldowns, however many other splits exit after it. Queued funds
+//! stop earning for their split; their rewards go to the splits still staked.
+
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    token, vec, Address, Env, IntoVal, InvokeError, Symbol, Val, Vec,
+};
+
+use crate::{
+    errors::Error,
+    events, state_machine, storage,
+    types::{Split, SplitStatus, StakingConfig, YieldPolicy, YieldPool, YieldPosition},
+};
+
+/// Fixed-point scale of `YieldPool::reward_index`, matching the staking contract.
+const REWARD_SCALE: i128 = 1_000_000_000_000;
+
+fn config(env: &Env) -> Result<StakingConfig, Error> {
+    storage::get_staking_config(env).ok_or(Error::StakingUnavailable)
+}
+
+fn call(env: &Env, config: &StakingConfig, fn_name: &str, args: Vec<Val>) {
+    env.invoke_contract::<()>(&config.contract, &Symbol::new(env, fn_name), args);
+}
+
+/// Start tracking `split_id` in yield mode. Its token must be the one the staking
+/// contract stakes.
+pub fn open_position(
+    env: &Env,
+    split_id: u64,
+    token: &Address,
+    policy: YieldPolicy,
+) -> Result<(), Error> {
+    if config(env)?.token != *token {
+        return Err(Error::StakingUnavailable);
+    }
+    let position = YieldPosition {
+        policy,
+        staked: 0,
+        unstaking: 0,
+        reward_index: storage::get_yield_pool(env).reward_index,
+        accrued: 0,
+        exit: SplitStatus::Pending,
+        batch: 0,
+    };
+    storage::set_yield_position(env, split_id, &position);
+    Ok(())
+}
+
+/// Funds of `split_id` currently staked or cooling down, and so not held by the escrow.
+pub fn locked(env: &Env, split_id: u64) -> i128 {
+    storage::get_yield_position(env, split_id)
+        .map(|p| p.staked + p.unstaking)
+        .unwrap_or(0)
+}
+
+/// Claim whatever the staking contract has accrued for the escrow and spread it over
+/// the currently staked amount. Must run before `total_staked` changes.
+fn harvest(env: &Env, config: &StakingConfig, pool: &mut YieldPool) {
+    if pool.total_staked == 0 {
+        return;
+    }
+    let this = env.current_contract_address();
+    let claimed = env.try_invoke_contract::<i128, InvokeError>(
+        &config.contract,
+        &Symbol::new(env, "claim_staking_rewards"),
+        (this,).into_val(env),
+    );
+    // The staking contract errors when there is nothing to claim.
+    if let Ok(Ok(rewards)) = claimed {
+        pool.reward_index += rewards * REWARD_SCALE / pool.total_staked;
+    }
+}
+
+fn accrue(pool: &YieldPool, position: &mut YieldPosition) {
+    position.accrued +=
+        position.staked * (pool.reward_index - position.reward_index) / REWARD_SCALE;
+    position.reward_index = pool.reward_index;
+}
+
+/// Pay out `position.accrued` per its policy. Depositors share pro rata to their
+/// balances, with the rounding remainder going to the first; with no balances left
+/// the creator is paid.
+fn pay(env: &Env, split: &Split, position: &mut YieldPosition) -> i128 {
+    let amount = position.accrued;
+    if amount <= 0 {
+        return 0;
+    }
+    let token_client = token::Client::new(env, &split.token);
+    let this = env.current_contract_address();
+    let mut total_balance = 0i128;
+    for (_, balance) in split.balances.iter() {
+        total_balance += balance;
+    }
+
+    if position.policy == YieldPolicy::Creator || total_balance <= 0 {
+        token_client.transfer(&this, &split.creator, &amount);
+    } else {
+        let mut paid = 0i128;
+        for (_, balance) in split.balances.iter() {
+            paid += balance * amount / total_balance;
+        }
+        let mut dust = amount - paid;
+        for (depositor, balance) in split.balances.iter() {
+            let share = balance * amount / total_balance + dust;
+            if share > 0 {
+                dust = 0;
+                token_client.transfer(&this, &depositor, &share);
+            }
+        }
+    }
+    position.accrued = 0;
+    events::emit_yield_paid(env, split.split_id, amount, &position.policy);
+    amount
+}
+
+/// Stake everything `split` holds that is not staked yet. Returns the amount staked.
+pub fn stake_idle(env: &Env, split: &Split) -> Result<i128, Error> {
+    let mut position =
+        storage::get_yield_position(env, split.split_id).ok_or(Error::YieldNotEnabled)?;
+    if !(state_machine::can_cancel(&split.status) && split.status != SplitStatus::Disputed) {
+        return Err(Error::SplitNotActive);
+    }
+    // Applying a pending amendment may refund deposits, which must stay in the escrow.
+    if storage::get_amendment(env, split.split_id).is_some() {
+        return Err(Error::AmendmentPending);
+    }
+    let idle = split.deposited_amount - split.released_amount - position.staked;
+    if idle <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    let config = config(env)?;
+    let mut pool = storage::get_yield_pool(env);
+    harvest(env, &config, &mut pool);
+    accrue(&pool, &mut position);
+
+    // The staking contract pulls the stake from us with a plain transfer.
+    let this = env.current_contract_address();
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: config.token.clone(),
+                fn_name: Symbol::new(env, "transfer"),
+                args: (this.clone(), config.contract.clone(), idle).into_val(env),
+            },
+            sub_invocations: vec![env],
+        }),
+    ]);
+    call(env, &config, "stake", (this, idle).into_val(env));
+
+    position.staked += idle;
+    pool.total_staked += idle;
+    storage::set_yield_pool(env, &pool);
+    storage::set_yield_position(env, split.split_id, &position);
+    events::emit_staked(env, split.split_id, idle);
+    Ok(idle)
+}
+
+/// Withdraw the batch in cooldown if its cooldown is over, then unstake everything
+/// queued behind it as the next batch. Nothing is unstaked while a batch is cooling
+/// down, since that would restart its cooldown.
+fn advance(env: &Env, config: &StakingConfig, pool: &mut YieldPool) {
+    let this = env.current_contract_address();
+    if pool.pending_withdrawal > 0 {
+        let token_client = token::Client::new(env, &config.token);
+        let before = token_client.balance(&this);
+        let withdrawn = env.try_invoke_contract::<(), InvokeError>(
+            &config.contract,
+            &Symbol::new(env, "withdraw"),
+            (this.clone(),).into_val(env),
+        );
+        if !matches!(withdrawn, Ok(Ok(()))) {
+            return;
+        }
+        pool.withdrawn += token_client.balance(&this) - before;
+        pool.pending_withdrawal = 0;
+    }
+    if pool.queued > 0 {
+        call(env, config, "unstake", (this, pool.queued).into_val(env));
+        pool.pending_withdrawal = pool.queued;
+        pool.queued = 0;
+        pool.batch += 1;
+    }
+}
+
+/// If `split` has funds staked, queue them for unstaking and move it to `Unstaking`,
+/// remembering `exit` for `complete_unstake`. Returns `false` when nothing is staked
+/// and the caller should settle immediately.
+pub fn begin_exit(env: &Env, split: &mut Split, exit: SplitStatus) -> Result<bool, Error> {
+    let mut position = match storage::get_yield_position(env, split.split_id) {
+        Some(position) if position.staked > 0 => position,
+        _ => return Ok(false),
+    };
+    let config = config(env)?;
+    let mut pool = storage::get_yield_pool(env);
+    harvest(env, &config, &mut pool);
+    accrue(&pool, &mut position);
+
+    let amount = position.staked;
+    pool.total_staked -= amount;
+    pool.queued += amount;
+    position.staked = 0;
+    position.unstaking = amount;
+    position.exit = exit.clone();
+    position.batch = pool.batch + 1;
+    advance(env, &config, &mut pool);
+    state_machine::apply(env, split, SplitStatus::Unstaking)?;
+    storage::set_yield_pool(env, &pool);
+    storage::set_yield_position(env, split.split_id, &position);
+    events::emit_unstaking(env, split.split_id, amount, &exit);
+    Ok(true)
+}
+
+/// Move the unstaking batches along and, once `split`'s batch has been withdrawn, pay
+/// out its rewards. Returns the status the split should move to, or `None` while its
+/// funds are still queued or cooling down.
+pub fn finish_exit(env: &Env, split: &Split) -> Result<Option<SplitStatus>, Error> {
+    let mut position =
+        storage::get_yield_position(env, split.split_id).ok_or(Error::YieldNotEnabled)?;
+    let config = config(env)?;
+    let mut pool = storage::get_yield_pool(env);
+
+    advance(env, &config, &mut pool);
+    let withdrawn = position.batch < pool.batch
+        || (position.batch == pool.batch && pool.pending_withdrawal == 0);
+    if !withdrawn {
+        storage::set_yield_pool(env, &pool);
+        return Ok(None);
+    }
+
+    pool.withdrawn -= position.unstaking;
+    position.unstaking = 0;
+    pay(env, split, &mut position);
+    storage::set_yield_pool(env, &pool);
+    storage::set_yield_position(env, split.split_id, &position);
+    Ok(Some(position.exit))
+}
+
+/// Harvest and pay out the rewards `split` has earned so far. Returns the amount paid.
+pub fn claim(env: &Env, split: &Split) -> Result<i128, Error> {
+    let mut position =
+        storage::get_yield_position(env, split.split_id).ok_or(Error::YieldNotEnabled)?;
+    let config = config(env)?;
+    let mut pool = storage::get_yield_pool(env);
+    harvest(env, &config, &mut pool);
+    accrue(&pool, &mut position);
+    let paid = pay(env, split, &mut position);
+    storage::set_yield_pool(env, &pool);
+    storage::set_yield_position(env, split.split_id, &position);
+    Ok(paid)
+}
@@ -1,10 +1,12 @@
 /// Escrow state machine for split-escrow.
 ///
 /// Centralises all valid state transitions and provides a single call-site for
-/// enforcing them, so `lib.rs` never mutates `SplitStatus` directly — it always
-/// goes through `transition`.  This makes invalid transitions immediately
-/// detectable and keeps lifecycle event emission co-located with the transition
-/// that caused it.
+/// enforcing them, so the rest of the crate never mutates `SplitStatus` directly —
+/// it always goes through `apply`.  `apply` checks the move with `transition` and
+/// then carries every side effect of a status change: the `StatusChanged` event,
+/// the move between status indexes and the stamp in the settlement log.  Calling
+/// `transition` on its own only validates, so a status set after it would skip
+/// those side effects.
 ///
 /// ## State graph
 ///
@@ -12,7 +14,10 @@
 ///  ┌─────────┐  deposit (obligations met)   ┌───────┐
 ///  │ Pending │ ────────────────────────────► │ Ready │
 ///  └─────────┘                               └───────┘
-///       │                                        │
+///       │  │ expire (anyone, past deadline)      │
+///       │  └──────────────► ┌─────────┐          │
+///       │                   │ Expired │          │
+///       │                   └─────────┘          │
 ///       │ cancel (creator)                       │ release (creator)
 ///       ▼                                        ▼
 ///  ┌───────────┐                          ┌──────────┐
@@ -24,26 +29,67 @@
 ///  ───────
 /// ```
 ///
-/// Terminal states (`Released`, `Cancelled`) have no outgoing transitions.
+/// Milestone splits move `Ready → PartiallyReleased` on the first tranche and
+/// `PartiallyReleased → Released` on the last; cancelling a `PartiallyReleased`
+/// split refunds only the unreleased remainder.
+///
+/// Any non-terminal split can be put on hold as `Disputed` by the registered
+/// dispute contract. A disputed split only leaves through that contract's
+/// callback to `reverse_split` (→ `Cancelled`) or to `lift_dispute_hold`, which
+/// returns it to the status it had before the hold. The admin may also lift a hold
+/// left unresolved for `HOLD_TIMEOUT`.
+///
+/// A yield-mode split with staked funds passes through `Unstaking` on its way out:
+/// releasing, cancelling or expiring it starts the staking cooldown, and
+/// `complete_unstake` then moves it to the intended terminal status.
+///
+/// Terminal states (`Released`, `Cancelled`, `Expired`) have no outgoing transitions.
+use soroban_sdk::Env;
+
 use crate::errors::Error;
-use crate::types::SplitStatus;
+use crate::events;
+use crate::receipts;
+use crate::storage;
+use crate::types::{Split, SplitStatus};
 
 /// Every valid (from, to) pair in the state machine.
 const ALLOWED_TRANSITIONS: &[(SplitStatus, SplitStatus)] = &[
     (SplitStatus::Pending, SplitStatus::Ready),
     (SplitStatus::Pending, SplitStatus::Cancelled),
+    (SplitStatus::Pending, SplitStatus::Expired),
     (SplitStatus::Ready, SplitStatus::Released),
     (SplitStatus::Ready, SplitStatus::Cancelled),
+    (SplitStatus::Ready, SplitStatus::PartiallyReleased),
+    (SplitStatus::PartiallyReleased, SplitStatus::Released),
+    (SplitStatus::PartiallyReleased, SplitStatus::Cancelled),
+    (SplitStatus::Pending, SplitStatus::Disputed),
+    (SplitStatus::Ready, SplitStatus::Disputed),
+    (SplitStatus::PartiallyReleased, SplitStatus::Disputed),
+    (SplitStatus::Disputed, SplitStatus::Pending),
+    (SplitStatus::Disputed, SplitStatus::Ready),
+    (SplitStatus::Disputed, SplitStatus::PartiallyReleased),
+    (SplitStatus::Disputed, SplitStatus::Cancelled),
+    (SplitStatus::Pending, SplitStatus::Unstaking),
+    (SplitStatus::Ready, SplitStatus::Unstaking),
+    (SplitStatus::PartiallyReleased, SplitStatus::Unstaking),
+    (SplitStatus::Disputed, SplitStatus::Unstaking),
+    (SplitStatus::Unstaking, SplitStatus::Released),
+    (SplitStatus::Unstaking, SplitStatus::Cancelled),
+    (SplitStatus::Unstaking, SplitStatus::Expired),
 ];
 
-/// Attempt to transition `current` to `next`.
+/// Check whether `current` may move to `next`. Validation only; use `apply` to
+/// actually change a split's status.
 ///
 /// Returns `Ok(())` when the transition is allowed; otherwise returns
 /// `Err(Error::SplitNotActive)` for terminal-state violations or
 /// `Err(Error::InvalidInput)` for any other forbidden move.
 pub fn transition(current: &SplitStatus, next: &SplitStatus) -> Result<(), Error> {
     // Terminal states may never be left.
-    if *current == SplitStatus::Released || *current == SplitStatus::Cancelled {
+    if *current == SplitStatus::Released
+        || *current == SplitStatus::Cancelled
+        || *current == SplitStatus::Expired
+    {
         return Err(Error::SplitNotActive);
     }
 
@@ -56,10 +102,28 @@ pub fn transition(current: &SplitStatus, next: &SplitStatus) -> Result<(), Error
     Err(Error::InvalidInput)
 }
 
+/// Move `split` to `next` if the transition is allowed, emitting a single
+/// `StatusChanged(split_id, from, to)` event, moving the split between
+/// status indexes and stamping the transition into its settlement log.  The
+/// caller persists the split itself.
+pub fn apply(env: &Env, split: &mut Split, next: SplitStatus) -> Result<(), Error> {
+    transition(&split.status, &next)?;
+    let from = split.status.clone();
+    split.status = next;
+    storage::unindex_by_status(env, &from, split.split_id);
+    storage::index_by_status(env, &split.status, split.split_id);
+    receipts::record_status(env, split.split_id, &split.status);
+    events::emit_status_changed(env, split.split_id, &from, &split.status);
+    Ok(())
+}
+
 /// Returns `true` when the status is one from which deposits and metadata
 /// updates are still accepted (i.e. the escrow is not yet finalised).
 pub fn is_active(status: &SplitStatus) -> bool {
-    *status == SplitStatus::Pending || *status == SplitStatus::Ready
+    *status == SplitStatus::Pending
+        || *status == SplitStatus::Ready
+        || *status == SplitStatus::PartiallyReleased
+        || *status == SplitStatus::Disputed
 }
 
 /// Returns `true` when funds can be deposited into the escrow.
@@ -67,14 +131,19 @@ pub fn accepts_deposits(status: &SplitStatus) -> bool {
     *status == SplitStatus::Pending
 }
 
-/// Returns `true` when the escrow can be released (all obligations met).
+/// Returns `true` when the escrow (or its next milestone) can be released.
+/// `Disputed` splits must have their hold lifted first.
 pub fn can_release(status: &SplitStatus) -> bool {
-    *status == SplitStatus::Ready
+    *status == SplitStatus::Ready || *status == SplitStatus::PartiallyReleased
 }
 
-/// Returns `true` when the escrow can be cancelled by its creator.
+/// Returns `true` when the escrow can be cancelled. `Disputed` splits additionally
+/// need the dispute contract's authorization.
 pub fn can_cancel(status: &SplitStatus) -> bool {
-    *status == SplitStatus::Pending || *status == SplitStatus::Ready
+    *status == SplitStatus::Pending
+        || *status == SplitStatus::Ready
+        || *status == SplitStatus::PartiallyReleased
+        || *status == SplitStatus::Disputed
 }
 
 #[cfg(test)]
@@ -101,6 +170,19 @@ mod tests {
         assert!(transition(&SplitStatus::Ready, &SplitStatus::Cancelled).is_ok());
     }
 
+    #[test]
+    fn milestone_transitions_allowed() {
+        assert!(transition(&SplitStatus::Ready, &SplitStatus::PartiallyReleased).is_ok());
+        assert!(transition(&SplitStatus::PartiallyReleased, &SplitStatus::Released).is_ok());
+        assert!(transition(&SplitStatus::PartiallyReleased, &SplitStatus::Cancelled).is_ok());
+    }
+
+    #[test]
+    fn partially_released_cannot_reopen() {
+        assert!(transition(&SplitStatus::PartiallyReleased, &SplitStatus::Pending).is_err());
+        assert!(transition(&SplitStatus::PartiallyReleased, &SplitStatus::Ready).is_err());
+    }
+
     #[test]
     fn released_is_terminal() {
         assert!(transition(&SplitStatus::Released, &SplitStatus::Pending).is_err());
@@ -113,6 +195,43 @@ mod tests {
         assert!(transition(&SplitStatus::Cancelled, &SplitStatus::Ready).is_err());
     }
 
+    #[test]
+    fn pending_to_expired_allowed() {
+        assert!(transition(&SplitStatus::Pending, &SplitStatus::Expired).is_ok());
+    }
+
+    #[test]
+    fn ready_to_expired_forbidden() {
+        assert!(transition(&SplitStatus::Ready, &SplitStatus::Expired).is_err());
+    }
+
+    #[test]
+    fn expired_is_terminal() {
+        assert!(transition(&SplitStatus::Expired, &SplitStatus::Pending).is_err());
+        assert!(transition(&SplitStatus::Expired, &SplitStatus::Cancelled).is_err());
+    }
+
+    #[test]
+    fn disputed_leaves_only_by_cancel_or_lifting_the_hold() {
+        assert!(transition(&SplitStatus::Ready, &SplitStatus::Disputed).is_ok());
+        assert!(transition(&SplitStatus::Disputed, &SplitStatus::Cancelled).is_ok());
+        assert!(transition(&SplitStatus::Disputed, &SplitStatus::Pending).is_ok());
+        assert!(transition(&SplitStatus::Disputed, &SplitStatus::Ready).is_ok());
+        assert!(transition(&SplitStatus::Disputed, &SplitStatus::PartiallyReleased).is_ok());
+        assert!(transition(&SplitStatus::Disputed, &SplitStatus::Released).is_err());
+        assert!(transition(&SplitStatus::Disputed, &SplitStatus::Disputed).is_err());
+    }
+
+    #[test]
+    fn unstaking_leads_only_to_a_terminal_status() {
+        assert!(transition(&SplitStatus::Pending, &SplitStatus::Unstaking).is_ok());
+        assert!(transition(&SplitStatus::Unstaking, &SplitStatus::Released).is_ok());
+        assert!(transition(&SplitStatus::Unstaking, &SplitStatus::Cancelled).is_ok());
+        assert!(transition(&SplitStatus::Unstaking, &SplitStatus::Expired).is_ok());
+        assert!(transition(&SplitStatus::Unstaking, &SplitStatus::Pending).is_err());
+        assert!(transition(&SplitStatus::Unstaking, &SplitStatus::Disputed).is_err());
+    }
+
     #[test]
     fn pending_to_released_forbidden() {
         assert!(transition(&SplitStatus::Pending, &SplitStatus::Released).is_err());
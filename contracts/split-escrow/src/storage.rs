@@ -1,22 +1,175 @@
-use soroban_sdk::{contracttype, Address, Env, String};
+use soroban_sdk::{contracttype, Address, Env, IntoVal, Map, String, TryFromVal, Val, Vec};
 
-use crate::types::Split;
+use crate::types::{
+    Amendment, CycleLink, DisputeHold, FeeSchedule, Limits, Permission, RecurringSplit, Role,
+    SettlementLog, Split, SplitStatus, StakingConfig, TtlPolicy, YieldPool, YieldPosition,
+};
 
+/// Default TTL policy: once an entry has fewer than `LEDGER_TTL_THRESHOLD` ledgers
+/// left, extend it to `LEDGER_TTL_PERSISTENT`.
 const LEDGER_TTL_PERSISTENT: u32 = 31_536_000;
 const LEDGER_TTL_THRESHOLD: u32 = 86_400;
 
+/// Most split ids stored in one index page entry.
+pub const INDEX_PAGE_SIZE: u32 = 50;
+
+/// A secondary index over split ids.
+#[contracttype]
+#[derive(Clone)]
+pub enum IndexKey {
+    /// Splits created by an address.
+    Creator(Address),
+    /// Splits in which an address holds an obligation.
+    Participant(Address),
+    /// Splits currently in a status.
+    Status(SplitStatus),
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Admin,
+    PendingAdmin,
+    RoleMember(Role, Address),
+    /// Contract-wide asset from schema v1; only read by `migration`.
     Token,
+    AllowedToken(Address),
     NextSplitId,
     Split(u64),
     WhitelistEnabled(u64),
     WhitelistMember(u64, Address),
+    NextRecurringId,
+    Recurring(u64),
+    CycleLink(u64),
+    /// Next deposit intent nonce for a participant.
+    IntentNonce(Address),
+    /// Pending obligation amendment for a split.
+    Amendment(u64),
+    /// Fees, payouts and status history of a split, for receipts.
+    SettlementLog(u64),
+    /// Contract allowed to put splits on dispute hold and resolve them.
+    DisputeContract,
+    /// Path-payment contract used for cross-asset deposits.
+    PathPaymentContract,
+    /// Staking contract and token used for yield mode.
+    StakingConfig,
+    /// The escrow's aggregate staking position.
+    YieldPool,
+    /// A yield-mode split's share of the pool.
+    YieldPosition(u64),
+    /// Co-managers of a split and the permissions each holds.
+    Managers(u64),
+    /// Nominee of a pending creator handover.
+    PendingCreator(u64),
+    /// Pre-dispute status of a split on dispute hold.
+    DisputeHold(u64),
+    /// Global circuit breaker.
+    Paused,
+    /// Per-split circuit breaker.
+    Frozen(u64),
     FeeBps,
+    FeeSchedule,
+    CreatorFeeBps(Address),
     Treasury,
     Version,
+    SchemaVersion,
+    MigrationCursor,
+    /// Number of split ids in a secondary index.
+    IndexLen(IndexKey),
+    /// Up to `INDEX_PAGE_SIZE` consecutive ids of a secondary index.
+    IndexPage(IndexKey, u32),
+    /// Position of a split id within a secondary index.
+    IndexSlot(IndexKey, u64),
+    TtlPolicy,
+    Limits,
+}
+
+pub fn get_limits(env: &Env) -> Option<Limits> {
+    env.storage().instance().get(&DataKey::Limits)
+}
+
+pub fn set_limits(env: &Env, limits: &Limits) {
+    env.storage().instance().set(&DataKey::Limits, limits);
+}
+
+pub fn get_ttl_policy(env: &Env) -> TtlPolicy {
+    env.storage()
+        .instance()
+        .get(&DataKey::TtlPolicy)
+        .unwrap_or(TtlPolicy {
+            threshold: LEDGER_TTL_THRESHOLD,
+            extend_to: LEDGER_TTL_PERSISTENT,
+        })
+}
+
+pub fn set_ttl_policy(env: &Env, policy: &TtlPolicy) {
+    env.storage().instance().set(&DataKey::TtlPolicy, policy);
+}
+
+/// Extend the contract instance (and with it every instance key) per the TTL policy.
+pub fn bump_instance(env: &Env) {
+    let policy = get_ttl_policy(env);
+    env.storage()
+        .instance()
+        .extend_ttl(policy.threshold, policy.extend_to);
+}
+
+fn bump(env: &Env, key: &DataKey) {
+    let policy = get_ttl_policy(env);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, policy.threshold, policy.extend_to);
+}
+
+/// Read a persistent entry, extending its TTL when it exists.
+fn get_persistent<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<V> {
+    let value = env.storage().persistent().get(key);
+    if value.is_some() {
+        bump(env, key);
+    }
+    value
+}
+
+fn has_persistent(env: &Env, key: &DataKey) -> bool {
+    let present = env.storage().persistent().has(key);
+    if present {
+        bump(env, key);
+    }
+    present
+}
+
+fn set_persistent<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+    env.storage().persistent().set(key, value);
+    bump(env, key);
+}
+
+/// Extend a split and every per-split entry that exists to the full policy TTL,
+/// regardless of how much they have left. Whitelist members are extended when read.
+pub fn extend_split_ttl(env: &Env, split_id: u64) -> bool {
+    let policy = get_ttl_policy(env);
+    let keys = [
+        DataKey::Split(split_id),
+        DataKey::WhitelistEnabled(split_id),
+        DataKey::CycleLink(split_id),
+        DataKey::Amendment(split_id),
+        DataKey::SettlementLog(split_id),
+        DataKey::Frozen(split_id),
+        DataKey::YieldPosition(split_id),
+        DataKey::Managers(split_id),
+        DataKey::PendingCreator(split_id),
+        DataKey::DisputeHold(split_id),
+    ];
+    if !env.storage().persistent().has(&keys[0]) {
+        return false;
+    }
+    for key in keys.iter() {
+        if env.storage().persistent().has(key) {
+            env.storage()
+                .persistent()
+                .extend_ttl(key, policy.extend_to, policy.extend_to);
+        }
+    }
+    true
 }
 
 pub fn set_version(env: &Env, version: &String) {
@@ -27,6 +180,42 @@ pub fn get_version(env: &Env) -> String {
     env.storage().instance().get(&DataKey::Version).unwrap()
 }
 
+/// Layout version of stored data. Deployments that predate the key are on schema 1.
+pub fn get_schema_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::SchemaVersion)
+        .unwrap_or(1u32)
+}
+
+pub fn set_schema_version(env: &Env, version: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::SchemaVersion, &version);
+}
+
+/// Next split id `migrate` will visit; ids start at 1.
+pub fn get_migration_cursor(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::MigrationCursor)
+        .unwrap_or(1u64)
+}
+
+pub fn set_migration_cursor(env: &Env, cursor: u64) {
+    env.storage()
+        .instance()
+        .set(&DataKey::MigrationCursor, &cursor);
+}
+
+pub fn clear_migration_cursor(env: &Env) {
+    env.storage().instance().remove(&DataKey::MigrationCursor);
+}
+
+pub fn get_legacy_token(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Token)
+}
+
 pub fn has_admin(env: &Env) -> bool {
     env.storage().instance().has(&DataKey::Admin)
 }
@@ -39,12 +228,46 @@ pub fn get_admin(env: &Env) -> Address {
     env.storage().instance().get(&DataKey::Admin).unwrap()
 }
 
-pub fn set_token(env: &Env, token: &Address) {
-    env.storage().instance().set(&DataKey::Token, token);
+pub fn set_pending_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::PendingAdmin, admin);
+}
+
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::PendingAdmin)
 }
 
-pub fn get_token(env: &Env) -> Address {
-    env.storage().instance().get(&DataKey::Token).unwrap()
+pub fn clear_pending_admin(env: &Env) {
+    env.storage().instance().remove(&DataKey::PendingAdmin);
+}
+
+pub fn set_role(env: &Env, role: &Role, account: &Address, granted: bool) {
+    let key = DataKey::RoleMember(*role, account.clone());
+    if granted {
+        env.storage().instance().set(&key, &true);
+    } else {
+        env.storage().instance().remove(&key);
+    }
+}
+
+pub fn has_role(env: &Env, role: &Role, account: &Address) -> bool {
+    env.storage()
+        .instance()
+        .has(&DataKey::RoleMember(*role, account.clone()))
+}
+
+pub fn set_token_allowed(env: &Env, token: &Address, allowed: bool) {
+    let key = DataKey::AllowedToken(token.clone());
+    if allowed {
+        env.storage().instance().set(&key, &true);
+    } else {
+        env.storage().instance().remove(&key);
+    }
+}
+
+pub fn is_token_allowed(env: &Env, token: &Address) -> bool {
+    env.storage()
+        .instance()
+        .has(&DataKey::AllowedToken(token.clone()))
 }
 
 pub fn get_next_split_id(env: &Env) -> u64 {
@@ -63,37 +286,199 @@ pub fn bump_next_split_id(env: &Env) {
 
 pub fn set_split(env: &Env, split: &Split) {
     let key = DataKey::Split(split.split_id);
-    env.storage().persistent().set(&key, split);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, LEDGER_TTL_PERSISTENT, LEDGER_TTL_PERSISTENT);
+    set_persistent(env, &key, split);
 }
 
 pub fn get_split(env: &Env, split_id: u64) -> Option<Split> {
-    env.storage().persistent().get(&DataKey::Split(split_id))
+    get_persistent(env, &DataKey::Split(split_id))
 }
 
 pub fn set_whitelist_enabled(env: &Env, split_id: u64, enabled: bool) {
     let key = DataKey::WhitelistEnabled(split_id);
-    env.storage().persistent().set(&key, &enabled);
+    set_persistent(env, &key, &enabled);
+}
+
+pub fn is_whitelist_enabled(env: &Env, split_id: u64) -> bool {
+    get_persistent(env, &DataKey::WhitelistEnabled(split_id)).unwrap_or(false)
+}
+
+pub fn get_next_recurring_id(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::NextRecurringId)
+        .unwrap_or(1u64)
+}
+
+pub fn bump_next_recurring_id(env: &Env) {
+    let next = get_next_recurring_id(env);
+    env.storage()
+        .instance()
+        .set(&DataKey::NextRecurringId, &(next + 1));
+}
+
+pub fn set_recurring(env: &Env, recurring: &RecurringSplit) {
+    let key = DataKey::Recurring(recurring.recurring_id);
+    set_persistent(env, &key, recurring);
+}
+
+pub fn get_recurring(env: &Env, recurring_id: u64) -> Option<RecurringSplit> {
+    get_persistent(env, &DataKey::Recurring(recurring_id))
+}
+
+pub fn set_cycle_link(env: &Env, split_id: u64, link: &CycleLink) {
+    let key = DataKey::CycleLink(split_id);
+    set_persistent(env, &key, link);
+}
+
+pub fn get_cycle_link(env: &Env, split_id: u64) -> Option<CycleLink> {
+    get_persistent(env, &DataKey::CycleLink(split_id))
+}
+
+pub fn set_amendment(env: &Env, split_id: u64, amendment: &Amendment) {
+    let key = DataKey::Amendment(split_id);
+    set_persistent(env, &key, amendment);
+}
+
+pub fn get_amendment(env: &Env, split_id: u64) -> Option<Amendment> {
+    get_persistent(env, &DataKey::Amendment(split_id))
+}
+
+pub fn remove_amendment(env: &Env, split_id: u64) {
     env.storage()
         .persistent()
-        .extend_ttl(&key, LEDGER_TTL_PERSISTENT, LEDGER_TTL_PERSISTENT);
+        .remove(&DataKey::Amendment(split_id));
 }
 
-pub fn is_whitelist_enabled(env: &Env, split_id: u64) -> bool {
+pub fn set_staking_config(env: &Env, config: &StakingConfig) {
+    env.storage()
+        .instance()
+        .set(&DataKey::StakingConfig, config);
+}
+
+pub fn get_staking_config(env: &Env) -> Option<StakingConfig> {
+    env.storage().instance().get(&DataKey::StakingConfig)
+}
+
+pub fn get_yield_pool(env: &Env) -> YieldPool {
+    env.storage()
+        .instance()
+        .get(&DataKey::YieldPool)
+        .unwrap_or_default()
+}
+
+pub fn set_yield_pool(env: &Env, pool: &YieldPool) {
+    env.storage().instance().set(&DataKey::YieldPool, pool);
+}
+
+pub fn get_yield_position(env: &Env, split_id: u64) -> Option<YieldPosition> {
+    get_persistent(env, &DataKey::YieldPosition(split_id))
+}
+
+pub fn set_yield_position(env: &Env, split_id: u64, position: &YieldPosition) {
+    set_persistent(env, &DataKey::YieldPosition(split_id), position);
+}
+
+pub fn get_managers(env: &Env, split_id: u64) -> Map<Address, Vec<Permission>> {
+    get_persistent(env, &DataKey::Managers(split_id)).unwrap_or(Map::new(env))
+}
+
+pub fn set_managers(env: &Env, split_id: u64, managers: &Map<Address, Vec<Permission>>) {
+    set_persistent(env, &DataKey::Managers(split_id), managers);
+}
+
+pub fn get_pending_creator(env: &Env, split_id: u64) -> Option<Address> {
+    get_persistent(env, &DataKey::PendingCreator(split_id))
+}
+
+pub fn set_pending_creator(env: &Env, split_id: u64, creator: &Address) {
+    set_persistent(env, &DataKey::PendingCreator(split_id), creator);
+}
+
+pub fn remove_pending_creator(env: &Env, split_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingCreator(split_id));
+}
+
+pub fn get_dispute_hold(env: &Env, split_id: u64) -> Option<DisputeHold> {
+    get_persistent(env, &DataKey::DisputeHold(split_id))
+}
+
+pub fn set_dispute_hold(env: &Env, split_id: u64, hold: &DisputeHold) {
+    set_persistent(env, &DataKey::DisputeHold(split_id), hold);
+}
+
+pub fn remove_dispute_hold(env: &Env, split_id: u64) {
     env.storage()
         .persistent()
-        .get(&DataKey::WhitelistEnabled(split_id))
+        .remove(&DataKey::DisputeHold(split_id));
+}
+
+pub fn get_intent_nonce(env: &Env, participant: &Address) -> u64 {
+    get_persistent(env, &DataKey::IntentNonce(participant.clone())).unwrap_or(0u64)
+}
+
+pub fn set_intent_nonce(env: &Env, participant: &Address, nonce: u64) {
+    let key = DataKey::IntentNonce(participant.clone());
+    set_persistent(env, &key, &nonce);
+}
+
+pub fn set_settlement_log(env: &Env, split_id: u64, log: &SettlementLog) {
+    let key = DataKey::SettlementLog(split_id);
+    set_persistent(env, &key, log);
+}
+
+pub fn get_settlement_log(env: &Env, split_id: u64) -> Option<SettlementLog> {
+    get_persistent(env, &DataKey::SettlementLog(split_id))
+}
+
+pub fn set_dispute_contract(env: &Env, contract: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::DisputeContract, contract);
+}
+
+pub fn get_dispute_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::DisputeContract)
+}
+
+pub fn set_path_payment_contract(env: &Env, contract: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::PathPaymentContract, contract);
+}
+
+pub fn get_path_payment_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::PathPaymentContract)
+}
+
+pub fn set_paused(env: &Env, paused: bool) {
+    env.storage().instance().set(&DataKey::Paused, &paused);
+}
+
+pub fn is_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::Paused)
         .unwrap_or(false)
 }
 
+pub fn set_frozen(env: &Env, split_id: u64, frozen: bool) {
+    let key = DataKey::Frozen(split_id);
+    if frozen {
+        set_persistent(env, &key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn is_frozen(env: &Env, split_id: u64) -> bool {
+    has_persistent(env, &DataKey::Frozen(split_id))
+}
+
 pub fn add_to_whitelist(env: &Env, split_id: u64, address: &Address) {
     let key = DataKey::WhitelistMember(split_id, address.clone());
-    env.storage().persistent().set(&key, &true);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, LEDGER_TTL_PERSISTENT, LEDGER_TTL_PERSISTENT);
+    set_persistent(env, &key, &true);
 }
 
 pub fn remove_from_whitelist(env: &Env, split_id: u64, address: &Address) {
@@ -103,9 +488,7 @@ pub fn remove_from_whitelist(env: &Env, split_id: u64, address: &Address) {
 }
 
 pub fn is_whitelisted(env: &Env, split_id: u64, address: &Address) -> bool {
-    env.storage()
-        .persistent()
-        .has(&DataKey::WhitelistMember(split_id, address.clone()))
+    has_persistent(env, &DataKey::WhitelistMember(split_id, address.clone()))
 }
 
 pub fn set_fee_bps(env: &Env, fee_bps: u32) {
@@ -119,6 +502,28 @@ pub fn get_fee_bps(env: &Env) -> u32 {
         .unwrap_or(0u32)
 }
 
+pub fn set_fee_schedule(env: &Env, schedule: &FeeSchedule) {
+    env.storage()
+        .instance()
+        .set(&DataKey::FeeSchedule, schedule);
+}
+
+pub fn get_fee_schedule(env: &Env) -> Option<FeeSchedule> {
+    env.storage().instance().get(&DataKey::FeeSchedule)
+}
+
+pub fn set_creator_fee_bps(env: &Env, creator: &Address, fee_bps: Option<u32>) {
+    let key = DataKey::CreatorFeeBps(creator.clone());
+    match fee_bps {
+        Some(bps) => set_persistent(env, &key, &bps),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_creator_fee_bps(env: &Env, creator: &Address) -> Option<u32> {
+    get_persistent(env, &DataKey::CreatorFeeBps(creator.clone()))
+}
+
 pub fn set_treasury(env: &Env, treasury: &Address) {
     env.storage().instance().set(&DataKey::Treasury, treasury);
 }
@@ -126,3 +531,113 @@ pub fn set_treasury(env: &Env, treasury: &Address) {
 pub fn get_treasury(env: &Env) -> Option<Address> {
     env.storage().instance().get(&DataKey::Treasury)
 }
+
+// Each index is stored as a length, fixed-size pages of ids and one slot entry per id, so
+// inserting or removing an id touches at most two pages however large the index grows.
+// Removal moves the last id into the freed position, so index order is not meaningful.
+
+pub fn index_len(env: &Env, key: &IndexKey) -> u32 {
+    get_persistent(env, &DataKey::IndexLen(key.clone())).unwrap_or(0)
+}
+
+fn get_index_page(env: &Env, key: &IndexKey, page: u32) -> Vec<u64> {
+    get_persistent(env, &DataKey::IndexPage(key.clone(), page)).unwrap_or(Vec::new(env))
+}
+
+fn set_index_page(env: &Env, key: &IndexKey, page: u32, ids: &Vec<u64>) {
+    let page_key = DataKey::IndexPage(key.clone(), page);
+    if ids.is_empty() {
+        env.storage().persistent().remove(&page_key);
+        return;
+    }
+    set_persistent(env, &page_key, ids);
+}
+
+fn set_index_len(env: &Env, key: &IndexKey, len: u32) {
+    let len_key = DataKey::IndexLen(key.clone());
+    if len == 0 {
+        env.storage().persistent().remove(&len_key);
+        return;
+    }
+    set_persistent(env, &len_key, &len);
+}
+
+fn index_insert(env: &Env, key: IndexKey, split_id: u64) {
+    let slot_key = DataKey::IndexSlot(key.clone(), split_id);
+    if has_persistent(env, &slot_key) {
+        return;
+    }
+    let len = index_len(env, &key);
+    let page = len / INDEX_PAGE_SIZE;
+    let mut ids = get_index_page(env, &key, page);
+    ids.push_back(split_id);
+    set_index_page(env, &key, page, &ids);
+    set_persistent(env, &slot_key, &len);
+    set_index_len(env, &key, len + 1);
+}
+
+fn index_remove(env: &Env, key: IndexKey, split_id: u64) {
+    let slot_key = DataKey::IndexSlot(key.clone(), split_id);
+    let Some(pos) = get_persistent::<u32>(env, &slot_key) else {
+        return;
+    };
+    env.storage().persistent().remove(&slot_key);
+
+    let last = index_len(env, &key) - 1;
+    let last_page = last / INDEX_PAGE_SIZE;
+    let mut last_ids = get_index_page(env, &key, last_page);
+    let moved = last_ids.pop_back().unwrap();
+    if pos != last {
+        let page = pos / INDEX_PAGE_SIZE;
+        if page == last_page {
+            last_ids.set(pos % INDEX_PAGE_SIZE, moved);
+        } else {
+            let mut ids = get_index_page(env, &key, page);
+            ids.set(pos % INDEX_PAGE_SIZE, moved);
+            set_index_page(env, &key, page, &ids);
+        }
+        set_persistent(env, &DataKey::IndexSlot(key.clone(), moved), &pos);
+    }
+    set_index_page(env, &key, last_page, &last_ids);
+    set_index_len(env, &key, last);
+}
+
+/// Ids at positions `start..end` of an index; `end` must not exceed `index_len`.
+pub fn index_range(env: &Env, key: &IndexKey, start: u32, end: u32) -> Vec<u64> {
+    let mut out = Vec::new(env);
+    let mut pos = start;
+    while pos < end {
+        let page = pos / INDEX_PAGE_SIZE;
+        let ids = get_index_page(env, key, page);
+        let page_end = end.min((page + 1) * INDEX_PAGE_SIZE);
+        while pos < page_end {
+            out.push_back(ids.get(pos % INDEX_PAGE_SIZE).unwrap());
+            pos += 1;
+        }
+    }
+    out
+}
+
+pub fn index_by_creator(env: &Env, creator: &Address, split_id: u64) {
+    index_insert(env, IndexKey::Creator(creator.clone()), split_id);
+}
+
+pub fn unindex_by_creator(env: &Env, creator: &Address, split_id: u64) {
+    index_remove(env, IndexKey::Creator(creator.clone()), split_id);
+}
+
+pub fn index_by_participant(env: &Env, participant: &Address, split_id: u64) {
+    index_insert(env, IndexKey::Participant(participant.clone()), split_id);
+}
+
+pub fn unindex_by_participant(env: &Env, participant: &Address, split_id: u64) {
+    index_remove(env, IndexKey::Participant(participant.clone()), split_id);
+}
+
+pub fn index_by_status(env: &Env, status: &SplitStatus, split_id: u64) {
+    index_insert(env, IndexKey::Status(status.clone()), split_id);
+}
+
+pub fn unindex_by_status(env: &Env, status: &SplitStatus, split_id: u64) {
+    index_remove(env, IndexKey::Status(status.clone()), split_id);
+}
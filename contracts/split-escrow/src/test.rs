@@ -1,10 +1,14 @@
 #![cfg(test)]
 extern crate std;
 
-use crate::{SplitEscrowContract, SplitEscrowContractClient, SplitStatus};
+use crate::{
+    AppliedFee, CreateEscrowParams, FeeSchedule, FeeTier, Payout, PayoutShare, Permission, Role,
+    SplitEscrowContract, SplitEscrowContractClient, SplitStatus, YieldPolicy,
+};
 use soroban_sdk::token::{Client as TokenClient, StellarAssetClient as TokenAdminClient};
 use soroban_sdk::{
-    testutils::Address as _, testutils::Events as _, Address, Env, IntoVal, Map, String, Vec,
+    testutils::Address as _, testutils::Events as _, testutils::Ledger as _, Address, Env, FromVal,
+    IntoVal, Map, String, TryFromVal, Vec,
 };
 
 fn metadata_map(env: &Env, entries: &[(&str, &str)]) -> Map<String, String> {
@@ -56,28 +60,52 @@ fn setup() -> (
     )
 }
 
+/// Params for a plain escrow: no payout table, default cap, no metadata, note or deadline.
+fn escrow_params(
+    env: &Env,
+    creator: &Address,
+    description: &str,
+    total_amount: i128,
+    token: &Address,
+    obligations: &Map<Address, i128>,
+) -> CreateEscrowParams {
+    CreateEscrowParams {
+        creator: creator.clone(),
+        description: String::from_str(env, description),
+        total_amount,
+        token: token.clone(),
+        obligations: obligations.clone(),
+        payouts: Vec::new(env),
+        milestones: Vec::new(env),
+        max_participants: None,
+        whitelist_enabled: false,
+        metadata: Map::new(env),
+        note: None,
+        deadline: None,
+        yield_policy: YieldPolicy::Disabled,
+    }
+}
+
 #[test]
 fn test_fee_deducted_and_sent_to_treasury_on_release() {
     let (env, client, admin, creator, participant, token_client, _) = setup();
     let treasury = Address::generate(&env);
-    client.set_treasury(&treasury);
-    client.set_fee(&250u32); // 2.5%
+    client.set_treasury(&admin, &treasury);
+    client.set_fee(&admin, &250u32); // 2.5%
 
     let mut obligations = Map::new(&env);
     obligations.set(participant.clone(), 10_000);
 
-    let split_id = client.create_escrow(
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "Dinner"),
-        &10_000,
-        &Map::new(&env),
+        "Dinner",
+        10_000,
+        &token_client.address,
         &obligations,
-        &None,
-        &false,
-        &None,
-    );
+    ));
     client.deposit(&split_id, &participant, &10_000);
-    client.release_funds(&split_id);
+    client.release_funds(&creator, &split_id);
 
     assert_eq!(token_client.balance(&treasury), 250);
     assert_eq!(token_client.balance(&creator), 1_000_000 + 9_750);
@@ -90,48 +118,44 @@ fn test_fee_deducted_and_sent_to_treasury_on_release() {
 
 #[test]
 fn test_admin_can_update_fee_and_treasury() {
-    let (env, client, _admin, creator, participant, token_client, _) = setup();
+    let (env, client, admin, creator, participant, token_client, _) = setup();
 
     let treasury_a = Address::generate(&env);
-    client.set_treasury(&treasury_a);
-    client.set_fee(&100u32);
+    client.set_treasury(&admin, &treasury_a);
+    client.set_fee(&admin, &100u32);
 
     let mut obligations_a = Map::new(&env);
     obligations_a.set(participant.clone(), 1_000);
 
-    let split_a = client.create_escrow(
+    let split_a = client.create_escrow(&escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "A"),
-        &1_000,
-        &Map::new(&env),
+        "A",
+        1_000,
+        &token_client.address,
         &obligations_a,
-        &None,
-        &false,
-        &None,
-    );
+    ));
     client.deposit(&split_a, &participant, &1_000);
-    client.release_funds(&split_a);
+    client.release_funds(&creator, &split_a);
     assert_eq!(token_client.balance(&treasury_a), 10);
 
     let treasury_b = Address::generate(&env);
-    client.set_treasury(&treasury_b);
-    client.set_fee(&300u32);
+    client.set_treasury(&admin, &treasury_b);
+    client.set_fee(&admin, &300u32);
 
     let mut obligations_b = Map::new(&env);
     obligations_b.set(participant.clone(), 2_000);
 
-    let split_b = client.create_escrow(
+    let split_b = client.create_escrow(&escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "B"),
-        &2_000,
-        &Map::new(&env),
+        "B",
+        2_000,
+        &token_client.address,
         &obligations_b,
-        &None,
-        &false,
-        &None,
-    );
+    ));
     client.deposit(&split_b, &participant, &2_000);
-    client.release_funds(&split_b);
+    client.release_funds(&creator, &split_b);
     assert_eq!(token_client.balance(&treasury_b), 60);
 }
 
@@ -140,36 +164,34 @@ fn test_set_fee_and_set_treasury_are_admin_only() {
     let (env, client, admin, _creator, _participant, _token_client, _token_admin) = setup();
 
     env.mock_all_auths();
-    client.set_fee(&123u32);
-    client.set_treasury(&Address::generate(&env));
+    client.set_fee(&admin, &123u32);
+    client.set_treasury(&admin, &Address::generate(&env));
 
     assert_ne!(admin, Address::generate(&env));
 }
 
 #[test]
 fn test_fees_collected_event_emitted() {
-    let (env, client, _admin, creator, participant, _token_client, _) = setup();
+    let (env, client, admin, creator, participant, token_client, _) = setup();
     let treasury = Address::generate(&env);
-    client.set_treasury(&treasury);
-    client.set_fee(&500u32);
+    client.set_treasury(&admin, &treasury);
+    client.set_fee(&admin, &500u32);
 
     let before_len = env.events().all().len();
 
     let mut obligations = Map::new(&env);
     obligations.set(participant.clone(), 1_000);
 
-    let split_id = client.create_escrow(
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "Event"),
-        &1_000,
-        &Map::new(&env),
+        "Event",
+        1_000,
+        &token_client.address,
         &obligations,
-        &None,
-        &false,
-        &None,
-    );
+    ));
     client.deposit(&split_id, &participant, &1_000);
-    client.release_funds(&split_id);
+    client.release_funds(&creator, &split_id);
 
     let after_len = env.events().all().len();
     assert!(after_len > before_len);
@@ -214,8 +236,8 @@ fn test_upgrade_version_non_admin_fails() {
 
 #[test]
 fn test_partial_deposits() {
-    let (env, client, _admin, creator, participant, token_client, _) = setup();
-    client.set_treasury(&Address::generate(&env));
+    let (env, client, admin, creator, participant, token_client, _) = setup();
+    client.set_treasury(&admin, &Address::generate(&env));
     let p2 = Address::generate(&env);
     let token_admin_client = TokenAdminClient::new(&env, &token_client.address);
     token_admin_client.mint(&p2, &1_000_000);
@@ -224,16 +246,14 @@ fn test_partial_deposits() {
     obligations.set(participant.clone(), 5_000);
     obligations.set(p2.clone(), 5_000);
 
-    let split_id = client.create_escrow(
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "Shared Bill"),
-        &10_000,
-        &Map::new(&env),
+        "Shared Bill",
+        10_000,
+        &token_client.address,
         &obligations,
-        &None,
-        &false,
-        &None,
-    );
+    ));
 
     // Participant 1 pays half their obligation.
     client.deposit(&split_id, &participant, &2_500);
@@ -255,7 +275,7 @@ fn test_partial_deposits() {
     assert_eq!(escrow.deposited_amount, 10_000);
     assert_eq!(escrow.status, SplitStatus::Ready);
 
-    client.release_funds(&split_id);
+    client.release_funds(&creator, &split_id);
     assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Released);
     assert_eq!(token_client.balance(&creator), 1_000_000 + 10_000);
 }
@@ -271,16 +291,14 @@ fn test_cancel_partial_refunds() {
     obligations.set(participant.clone(), 5_000);
     obligations.set(p2.clone(), 5_000);
 
-    let split_id = client.create_escrow(
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "Shared Bill"),
-        &10_000,
-        &Map::new(&env),
+        "Shared Bill",
+        10_000,
+        &token_client.address,
         &obligations,
-        &None,
-        &false,
-        &None,
-    );
+    ));
 
     client.deposit(&split_id, &participant, &3_000);
     client.deposit(&split_id, &p2, &2_000);
@@ -288,7 +306,7 @@ fn test_cancel_partial_refunds() {
     let balance_p1_before = token_client.balance(&participant);
     let balance_p2_before = token_client.balance(&p2);
 
-    client.cancel_split(&split_id);
+    client.cancel_split(&creator, &split_id);
 
     assert_eq!(
         token_client.balance(&participant),
@@ -307,28 +325,26 @@ fn test_upgrade_version_invalid_semver_fails() {
 
 #[test]
 fn test_toggle_whitelist_allows_creator_to_restrict_access() {
-    let (env, client, _admin, creator, participant, _token_client, _token_admin) = setup();
+    let (env, client, _admin, creator, participant, token_client, _token_admin) = setup();
 
     let mut obligations = Map::new(&env);
     obligations.set(participant.clone(), 2_000);
 
-    let split_id = client.create_escrow(
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "Restricted"),
-        &2_000,
-        &Map::new(&env),
+        "Restricted",
+        2_000,
+        &token_client.address,
         &obligations,
-        &None,
-        &false,
-        &None,
-    );
+    ));
 
     // Default: whitelist is disabled.
     client.deposit(&split_id, &participant, &1_000);
     assert_eq!(client.get_escrow(&split_id).deposited_amount, 1_000);
 
     // Creator enables whitelist.
-    client.toggle_whitelist(&split_id, &true);
+    client.toggle_whitelist(&creator, &split_id, &true);
 
     // Second deposit (from same participant or another) should fail if not whitelisted.
     // We'll create a new participant and attempt to deposit.
@@ -337,9 +353,35 @@ fn test_toggle_whitelist_allows_creator_to_restrict_access() {
     assert!(res.is_err());
 }
 
+#[test]
+fn test_whitelist_can_be_enabled_at_creation() {
+    let (env, client, _admin, creator, participant, token_client, _token_admin) = setup();
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 2_000);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Restricted from the start",
+        2_000,
+        &token_client.address,
+        &obligations,
+    );
+    params.whitelist_enabled = true;
+    let split_id = client.create_escrow(&params);
+
+    assert_eq!(
+        client.try_deposit(&split_id, &participant, &1_000),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+    client.add_to_whitelist(&creator, &split_id, &participant);
+    client.deposit(&split_id, &participant, &1_000);
+    assert_eq!(client.get_escrow(&split_id).deposited_amount, 1_000);
+}
+
 #[test]
 fn test_create_escrow_with_metadata_stores_correctly() {
-    let (env, client, _admin, creator, participant, _token_client, _token_admin) = setup();
+    let (env, client, _admin, creator, participant, token_client, _token_admin) = setup();
     let mut metadata = soroban_sdk::Map::new(&env);
     metadata.set(
         String::from_str(&env, "key"),
@@ -349,16 +391,16 @@ fn test_create_escrow_with_metadata_stores_correctly() {
     let mut obligations = Map::new(&env);
     obligations.set(participant.clone(), 1_000);
 
-    let split_id = client.create_escrow(
+    let mut params = escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "Metadata test"),
-        &1_000,
-        &metadata,
+        "Metadata test",
+        1_000,
+        &token_client.address,
         &obligations,
-        &None,
-        &false,
-        &None,
     );
+    params.metadata = metadata.clone();
+    let split_id = client.create_escrow(&params);
 
     let escrow = client.get_escrow(&split_id);
     assert_eq!(escrow.metadata, metadata);
@@ -392,19 +434,17 @@ fn test_contract_upgraded_event_emitted() {
 
 #[test]
 fn test_default_max_participants_is_50() {
-    let (env, client, _admin, creator, participant, _tc, token_admin) = setup();
+    let (env, client, _admin, creator, participant, token_client, token_admin) = setup();
     let mut obligations = Map::new(&env);
     obligations.set(participant.clone(), 100);
-    let escrow_id = client.create_escrow(
+    let escrow_id = client.create_escrow(&escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "Cap default"),
-        &100,
-        &Map::new(&env),
+        "Cap default",
+        100,
+        &token_client.address,
         &obligations,
-        &None,
-        &false,
-        &None,
-    );
+    ));
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.max_participants, 50);
     assert_eq!(escrow.participants.len(), 0);
@@ -414,30 +454,39 @@ fn test_default_max_participants_is_50() {
 
 #[test]
 fn test_explicit_max_participants_stored_in_get_escrow() {
-    let (env, client, _admin, creator, p1, _tc, _ta) = setup();
+    let (env, client, _admin, creator, p1, token_client, _ta) = setup();
     let cap = 3u32;
     let mut obligations = Map::new(&env);
     obligations.set(p1.clone(), 300);
-    let escrow_id = client.create_escrow(
+    let mut params = escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "Explicit cap"),
-        &300,
-        &Map::new(&env),
+        "Explicit cap",
+        300,
+        &token_client.address,
         &obligations,
-        &Some(cap),
-        &false,
-        &None,
     );
+    params.max_participants = Some(cap);
+    let escrow_id = client.create_escrow(&params);
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.max_participants, cap);
     client.deposit(&escrow_id, &p1, &100);
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.participants.len(), 1);
+
+    // Explicit caps must stay within the configured participant limit.
+    for cap in [0u32, 51] {
+        params.max_participants = Some(cap);
+        assert_eq!(
+            client.try_create_escrow(&params),
+            Err(Ok(crate::Error::InvalidInput))
+        );
+    }
 }
 
 #[test]
 fn test_deposit_rejected_when_participant_cap_exceeded() {
-    let (env, client, _admin, creator, p1, _tc, token_admin) = setup();
+    let (env, client, _admin, creator, p1, token_client, token_admin) = setup();
     let p2 = Address::generate(&env);
     let p3 = Address::generate(&env);
     token_admin.mint(&p2, &10_000);
@@ -448,16 +497,16 @@ fn test_deposit_rejected_when_participant_cap_exceeded() {
     obligations.set(p2.clone(), 1_000);
     obligations.set(p3.clone(), 1_000);
 
-    let escrow_id = client.create_escrow(
+    let mut params = escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "Two max"),
-        &3_000,
-        &Map::new(&env),
+        "Two max",
+        3_000,
+        &token_client.address,
         &obligations,
-        &Some(2u32),
-        &false,
-        &None,
     );
+    params.max_participants = Some(2u32);
+    let escrow_id = client.create_escrow(&params);
 
     client.deposit(&escrow_id, &p1, &1_000);
     client.deposit(&escrow_id, &p2, &1_000);
@@ -473,48 +522,48 @@ fn test_deposit_rejected_when_participant_cap_exceeded() {
 
 #[test]
 fn test_existing_participant_can_deposit_again_without_increasing_count() {
-    let (env, client, _admin, creator, p1, _tc, _ta) = setup();
+    let (env, client, admin, creator, p1, token_client, _ta) = setup();
     // release_funds runs fee collection; treasury must be set even when fee bps is 0.
-    client.set_treasury(&Address::generate(&env));
+    client.set_treasury(&admin, &Address::generate(&env));
 
     let mut obligations = Map::new(&env);
     obligations.set(p1.clone(), 2_000);
 
-    let escrow_id = client.create_escrow(
+    let mut params = escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "Repeat"),
-        &2_000,
-        &Map::new(&env),
+        "Repeat",
+        2_000,
+        &token_client.address,
         &obligations,
-        &Some(1u32),
-        &false,
-        &None,
     );
+    params.max_participants = Some(1u32);
+    let escrow_id = client.create_escrow(&params);
     client.deposit(&escrow_id, &p1, &1_000);
     client.deposit(&escrow_id, &p1, &1_000);
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.participants.len(), 1);
     assert_eq!(escrow.deposited_amount, 2_000);
-    client.release_funds(&escrow_id);
+    client.release_funds(&creator, &escrow_id);
     assert_eq!(client.get_escrow(&escrow_id).status, SplitStatus::Released);
 }
 
 #[test]
 fn test_note_stored_on_create_and_get_note() {
-    let (env, client, _admin, creator, participant, _tc, _ta) = setup();
+    let (env, client, _admin, creator, participant, token_client, _ta) = setup();
     let text = "Dinner at Luigi's — Friday night";
     let mut obligations = Map::new(&env);
     obligations.set(participant.clone(), 100);
-    let split_id = client.create_escrow(
+    let mut params = escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "Bill"),
-        &100,
-        &Map::new(&env),
+        "Bill",
+        100,
+        &token_client.address,
         &obligations,
-        &None,
-        &false,
-        &Some(String::from_str(&env, text)),
     );
+    params.note = Some(String::from_str(&env, text));
+    let split_id = client.create_escrow(&params);
     assert_eq!(client.get_note(&split_id), String::from_str(&env, text));
     assert_eq!(
         client.get_escrow(&split_id).note,
@@ -524,41 +573,39 @@ fn test_note_stored_on_create_and_get_note() {
 
 #[test]
 fn test_creator_can_update_note_while_pending_and_ready() {
-    let (env, client, _admin, creator, p1, _tc, _ta) = setup();
-    client.set_treasury(&Address::generate(&env));
+    let (env, client, admin, creator, p1, token_client, _ta) = setup();
+    client.set_treasury(&admin, &Address::generate(&env));
 
     let mut obligations = Map::new(&env);
     obligations.set(p1.clone(), 2_000);
 
-    let split_id = client.create_escrow(
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "X"),
-        &2_000,
-        &Map::new(&env),
+        "X",
+        2_000,
+        &token_client.address,
         &obligations,
-        &None,
-        &false,
-        &None,
-    );
-    client.set_note(&split_id, &String::from_str(&env, "v1"));
+    ));
+    client.set_note(&creator, &split_id, &String::from_str(&env, "v1"));
     assert_eq!(client.get_note(&split_id), String::from_str(&env, "v1"));
 
     client.deposit(&split_id, &p1, &1_000);
-    client.set_note(&split_id, &String::from_str(&env, "v2-ready"));
+    client.set_note(&creator, &split_id, &String::from_str(&env, "v2-ready"));
     assert_eq!(
         client.get_note(&split_id),
         String::from_str(&env, "v2-ready")
     );
 
     client.deposit(&split_id, &p1, &1_000);
-    client.release_funds(&split_id);
-    let res = client.try_set_note(&split_id, &String::from_str(&env, "late"));
+    client.release_funds(&creator, &split_id);
+    let res = client.try_set_note(&creator, &split_id, &String::from_str(&env, "late"));
     assert!(res.is_err());
 }
 
 #[test]
 fn test_note_over_128_bytes_rejected_on_create_and_set() {
-    let (env, client, _admin, creator, participant, _tc, _ta) = setup();
+    let (env, client, _admin, creator, participant, token_client, _ta) = setup();
     let bytes = [b'a'; 129];
     let long = String::from_str(&env, core::str::from_utf8(&bytes).unwrap());
     assert_eq!(long.len(), 129);
@@ -566,71 +613,65 @@ fn test_note_over_128_bytes_rejected_on_create_and_set() {
     let mut obligations_try = Map::new(&env);
     obligations_try.set(participant.clone(), 100);
 
-    let res = client.try_create_escrow(
+    let mut params = escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "x"),
-        &100,
-        &Map::new(&env),
+        "x",
+        100,
+        &token_client.address,
         &obligations_try,
-        &None,
-        &false,
-        &Some(long.clone()),
     );
+    params.note = Some(long.clone());
+    let res = client.try_create_escrow(&params);
     assert!(res.is_err());
 
     let mut obligations_ok = Map::new(&env);
     obligations_ok.set(participant.clone(), 100);
 
-    let split_id = client.create_escrow(
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "ok"),
-        &100,
-        &Map::new(&env),
+        "ok",
+        100,
+        &token_client.address,
         &obligations_ok,
-        &None,
-        &false,
-        &None,
-    );
-    let res2 = client.try_set_note(&split_id, &long);
+    ));
+    let res2 = client.try_set_note(&creator, &split_id, &long);
     assert!(res2.is_err());
 }
 
 #[test]
 fn test_note_updated_emits_event() {
-    let (env, client, _admin, creator, participant, _tc, _ta) = setup();
+    let (env, client, _admin, creator, participant, token_client, _ta) = setup();
     let mut obligations = Map::new(&env);
     obligations.set(participant.clone(), 100);
-    let split_id = client.create_escrow(
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "E"),
-        &100,
-        &Map::new(&env),
+        "E",
+        100,
+        &token_client.address,
         &obligations,
-        &None,
-        &false,
-        &None,
-    );
+    ));
     let before = env.events().all().len();
-    client.set_note(&split_id, &String::from_str(&env, "hello"));
+    client.set_note(&creator, &split_id, &String::from_str(&env, "hello"));
     assert!(env.events().all().len() > before);
 }
 
 #[test]
 fn test_cancel_split() {
-    let (env, client, _admin, creator, participant, _tc, token_admin) = setup();
+    let (env, client, _admin, creator, participant, token_client, token_admin) = setup();
     let mut obligations = Map::new(&env);
     obligations.set(participant.clone(), 100);
-    let split_id = client.create_escrow(
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
         &creator,
-        &String::from_str(&env, "Cancel test"),
-        &100,
-        &Map::new(&env),
+        "Cancel test",
+        100,
+        &token_client.address,
         &obligations,
-        &None,
-        &false,
-        &None,
-    );
-    client.cancel_split(&split_id);
+    ));
+    client.cancel_split(&creator, &split_id);
 
     let escrow = client.get_escrow(&split_id);
     assert_eq!(escrow.status, SplitStatus::Cancelled);
@@ -638,10 +679,10 @@ fn test_cancel_split() {
     let res = client.try_deposit(&split_id, &participant, &100);
     assert!(res.is_err());
 
-    let res = client.try_release_funds(&split_id);
+    let res = client.try_release_funds(&creator, &split_id);
     assert!(res.is_err());
 
-    let res = client.try_set_note(&split_id, &String::from_str(&env, "denied"));
+    let res = client.try_set_note(&creator, &split_id, &String::from_str(&env, "denied"));
     assert!(res.is_err());
 
     let after = env.events().all().len();
@@ -649,3 +690,2919 @@ fn test_cancel_split() {
 
     let _ = token_admin;
 }
+
+#[test]
+fn test_expire_split_refunds_after_deadline() {
+    let (env, client, _admin, creator, participant, token_client, token_admin) = setup();
+    let p2 = Address::generate(&env);
+    token_admin.mint(&p2, &1_000_000);
+    env.ledger().set_timestamp(1_000);
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 5_000);
+    obligations.set(p2.clone(), 5_000);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Expiring",
+        10_000,
+        &token_client.address,
+        &obligations,
+    );
+    params.deadline = Some(2_000u64);
+    let split_id = client.create_escrow(&params);
+
+    client.deposit(&split_id, &participant, &3_000);
+    client.deposit(&split_id, &p2, &1_000);
+
+    // Not yet past the deadline.
+    env.ledger().set_timestamp(2_000);
+    let res = client.try_expire_split(&split_id);
+    assert_eq!(res, Err(Ok(crate::Error::DeadlineNotReached)));
+
+    env.ledger().set_timestamp(2_001);
+    let res = client.try_deposit(&split_id, &participant, &1_000);
+    assert_eq!(res, Err(Ok(crate::Error::DeadlinePassed)));
+
+    client.expire_split(&split_id);
+    assert_eq!(token_client.balance(&participant), 1_000_000);
+    assert_eq!(token_client.balance(&p2), 1_000_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    let escrow = client.get_escrow(&split_id);
+    assert_eq!(escrow.status, SplitStatus::Expired);
+    assert_eq!(escrow.deposited_amount, 0);
+
+    // Expired is terminal.
+    assert!(client.try_expire_split(&split_id).is_err());
+    assert!(client.try_cancel_split(&creator, &split_id).is_err());
+}
+
+#[test]
+fn test_expire_split_without_deadline_fails() {
+    let (env, client, _admin, creator, participant, token_client, _ta) = setup();
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 100);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Open-ended",
+        100,
+        &token_client.address,
+        &obligations,
+    ));
+    env.ledger().set_timestamp(u64::MAX);
+    let res = client.try_expire_split(&split_id);
+    assert_eq!(res, Err(Ok(crate::Error::DeadlineNotReached)));
+}
+
+#[test]
+fn test_create_escrow_with_past_deadline_fails() {
+    let (env, client, _admin, creator, participant, token_client, _ta) = setup();
+    env.ledger().set_timestamp(5_000);
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 100);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Late",
+        100,
+        &token_client.address,
+        &obligations,
+    );
+    params.deadline = Some(4_999u64);
+    let res = client.try_create_escrow(&params);
+    assert_eq!(res, Err(Ok(crate::Error::DeadlinePassed)));
+}
+
+#[test]
+fn test_withdraw_deposit_partial_and_full() {
+    let (env, client, _admin, creator, participant, token_client, _ta) = setup();
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 5_000);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Overpaid",
+        5_000,
+        &token_client.address,
+        &obligations,
+    ));
+
+    client.deposit(&split_id, &participant, &4_000);
+    client.withdraw_deposit(&split_id, &participant, &1_500);
+    let escrow = client.get_escrow(&split_id);
+    assert_eq!(escrow.deposited_amount, 2_500);
+    assert_eq!(escrow.balances.get(participant.clone()).unwrap(), 2_500);
+    assert_eq!(escrow.participants.len(), 1);
+    assert_eq!(token_client.balance(&participant), 1_000_000 - 2_500);
+
+    let before = env.events().all().len();
+    client.withdraw_deposit(&split_id, &participant, &2_500);
+    assert!(env.events().all().len() > before);
+    let escrow = client.get_escrow(&split_id);
+    assert_eq!(escrow.deposited_amount, 0);
+    assert_eq!(escrow.balances.get(participant.clone()), None);
+    assert_eq!(escrow.participants.len(), 0);
+    assert_eq!(token_client.balance(&participant), 1_000_000);
+    assert_eq!(escrow.status, SplitStatus::Pending);
+}
+
+#[test]
+fn test_withdraw_deposit_rejects_overdraw_and_non_pending() {
+    let (env, client, _admin, creator, participant, token_client, _ta) = setup();
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_000);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Full",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+
+    client.deposit(&split_id, &participant, &600);
+    let res = client.try_withdraw_deposit(&split_id, &participant, &601);
+    assert_eq!(res, Err(Ok(crate::Error::InsufficientBalance)));
+
+    client.deposit(&split_id, &participant, &400);
+    let res = client.try_withdraw_deposit(&split_id, &participant, &100);
+    assert_eq!(res, Err(Ok(crate::Error::SplitNotPending)));
+}
+
+#[test]
+fn test_split_settles_in_its_own_allowlisted_token() {
+    let (env, client, admin, creator, participant, token_client, _ta) = setup();
+    let treasury = Address::generate(&env);
+    client.set_treasury(&admin, &treasury);
+    client.set_fee(&admin, &100u32);
+
+    let eurc_admin = Address::generate(&env);
+    let eurc = env.register_stellar_asset_contract_v2(eurc_admin).address();
+    let eurc_client = TokenClient::new(&env, &eurc);
+    TokenAdminClient::new(&env, &eurc).mint(&participant, &5_000);
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 5_000);
+
+    let res = client.try_create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "EURC dinner",
+        5_000,
+        &eurc,
+        &obligations,
+    ));
+    assert_eq!(res, Err(Ok(crate::Error::TokenNotAllowed)));
+
+    client.set_token_allowed(&eurc, &true);
+    assert!(client.is_token_allowed(&eurc));
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "EURC dinner",
+        5_000,
+        &eurc,
+        &obligations,
+    ));
+    assert_eq!(client.get_escrow(&split_id).token, eurc);
+
+    // Delisting only affects new splits.
+    client.set_token_allowed(&eurc, &false);
+    client.deposit(&split_id, &participant, &5_000);
+    client.release_funds(&creator, &split_id);
+
+    assert_eq!(eurc_client.balance(&creator), 4_950);
+    assert_eq!(eurc_client.balance(&treasury), 50);
+    assert_eq!(token_client.balance(&creator), 1_000_000);
+    assert_eq!(token_client.balance(&participant), 1_000_000);
+}
+
+fn payout(recipient: &Address, share: PayoutShare) -> Payout {
+    Payout {
+        recipient: recipient.clone(),
+        share,
+    }
+}
+
+#[test]
+fn test_release_distributes_bps_payouts_with_remainder_to_first() {
+    let (env, client, admin, creator, participant, token_client, _ta) = setup();
+    let treasury = Address::generate(&env);
+    client.set_treasury(&admin, &treasury);
+    client.set_fee(&admin, &100u32); // 1%
+
+    let venue = Address::generate(&env);
+    let host = Address::generate(&env);
+    let tips = Address::generate(&env);
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_001);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Party",
+        1_001,
+        &token_client.address,
+        &obligations,
+    );
+    params.payouts = Vec::from_array(
+        &env,
+        [
+            payout(&venue, PayoutShare::Bps(3_333)),
+            payout(&host, PayoutShare::Bps(3_333)),
+            payout(&tips, PayoutShare::Bps(3_334)),
+        ],
+    );
+    let split_id = client.create_escrow(&params);
+    client.deposit(&split_id, &participant, &1_001);
+
+    let escrow_events = |env: &Env| {
+        env.events()
+            .all()
+            .iter()
+            .filter(|e| e.0 == client.address)
+            .count()
+    };
+    let before = escrow_events(&env);
+    client.release_funds(&creator, &split_id);
+    // FeesCollected + one payout per recipient + StatusChanged + released.
+    assert_eq!(escrow_events(&env), before + 6);
+
+    // fee = 10, net = 991; 991 * 3333 / 10000 = 330, 991 * 3334 / 10000 = 330.
+    assert_eq!(token_client.balance(&treasury), 10);
+    assert_eq!(token_client.balance(&venue), 331);
+    assert_eq!(token_client.balance(&host), 330);
+    assert_eq!(token_client.balance(&tips), 330);
+    assert_eq!(token_client.balance(&creator), 1_000_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_release_distributes_fixed_payouts_net_of_fee() {
+    let (env, client, admin, creator, participant, token_client, _ta) = setup();
+    let treasury = Address::generate(&env);
+    client.set_treasury(&admin, &treasury);
+    client.set_fee(&admin, &1_000u32); // 10%
+
+    let venue = Address::generate(&env);
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_000);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Dinner",
+        1_000,
+        &token_client.address,
+        &obligations,
+    );
+    params.payouts = Vec::from_array(
+        &env,
+        [
+            payout(&venue, PayoutShare::Fixed(800)),
+            payout(&creator, PayoutShare::Fixed(200)),
+        ],
+    );
+    let split_id = client.create_escrow(&params);
+    client.deposit(&split_id, &participant, &1_000);
+    client.release_funds(&creator, &split_id);
+
+    assert_eq!(token_client.balance(&treasury), 100);
+    assert_eq!(token_client.balance(&venue), 720);
+    assert_eq!(token_client.balance(&creator), 1_000_000 + 180);
+}
+
+#[test]
+fn test_invalid_payout_tables_rejected() {
+    let (env, client, _admin, creator, participant, token_client, _ta) = setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_000);
+
+    let tables = [
+        // Bps not summing to 10_000.
+        Vec::from_array(
+            &env,
+            [
+                payout(&a, PayoutShare::Bps(5_000)),
+                payout(&b, PayoutShare::Bps(4_000)),
+            ],
+        ),
+        // Fixed not summing to total.
+        Vec::from_array(
+            &env,
+            [
+                payout(&a, PayoutShare::Fixed(500)),
+                payout(&b, PayoutShare::Fixed(400)),
+            ],
+        ),
+        // Mixed share kinds.
+        Vec::from_array(
+            &env,
+            [
+                payout(&a, PayoutShare::Bps(5_000)),
+                payout(&b, PayoutShare::Fixed(500)),
+            ],
+        ),
+        // Duplicate recipient.
+        Vec::from_array(
+            &env,
+            [
+                payout(&a, PayoutShare::Bps(5_000)),
+                payout(&a, PayoutShare::Bps(5_000)),
+            ],
+        ),
+        // Zero weight.
+        Vec::from_array(
+            &env,
+            [
+                payout(&a, PayoutShare::Fixed(1_000)),
+                payout(&b, PayoutShare::Fixed(0)),
+            ],
+        ),
+    ];
+    for table in tables {
+        let mut params = escrow_params(
+            &env,
+            &creator,
+            "Bad",
+            1_000,
+            &token_client.address,
+            &obligations,
+        );
+        params.payouts = table;
+        let res = client.try_create_escrow(&params);
+        assert_eq!(res, Err(Ok(crate::Error::InvalidPayouts)));
+    }
+}
+
+#[test]
+fn test_release_milestones_in_order() {
+    let (env, client, admin, creator, participant, token_client, _ta) = setup();
+    let treasury = Address::generate(&env);
+    client.set_treasury(&admin, &treasury);
+    client.set_fee(&admin, &1_000u32); // 10%
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 6_000);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Phased",
+        6_000,
+        &token_client.address,
+        &obligations,
+    );
+    params.milestones = Vec::from_array(&env, [1_000i128, 2_000, 3_000]);
+    let split_id = client.create_escrow(&params);
+    client.deposit(&split_id, &participant, &6_000);
+
+    let res = client.try_release_milestone(&creator, &split_id, &1);
+    assert_eq!(res, Err(Ok(crate::Error::MilestoneOutOfOrder)));
+
+    client.release_milestone(&creator, &split_id, &0);
+    let escrow = client.get_escrow(&split_id);
+    assert_eq!(escrow.status, SplitStatus::PartiallyReleased);
+    assert_eq!(escrow.released_amount, 1_000);
+    assert_eq!(token_client.balance(&creator), 1_000_000 + 900);
+    assert_eq!(token_client.balance(&treasury), 100);
+
+    client.release_milestone(&creator, &split_id, &1);
+    client.release_milestone(&creator, &split_id, &2);
+    let escrow = client.get_escrow(&split_id);
+    assert_eq!(escrow.status, SplitStatus::Released);
+    assert_eq!(escrow.milestones_released, 3);
+    assert_eq!(token_client.balance(&creator), 1_000_000 + 5_400);
+    assert_eq!(token_client.balance(&treasury), 600);
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    assert!(client
+        .try_release_milestone(&creator, &split_id, &2)
+        .is_err());
+}
+
+#[test]
+fn test_cancel_after_partial_release_refunds_remainder_pro_rata() {
+    let (env, client, admin, creator, participant, token_client, token_admin) = setup();
+    client.set_treasury(&admin, &Address::generate(&env));
+    let p2 = Address::generate(&env);
+    token_admin.mint(&p2, &1_000_000);
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 2_000);
+    obligations.set(p2.clone(), 1_000);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Phased",
+        3_000,
+        &token_client.address,
+        &obligations,
+    );
+    params.milestones = Vec::from_array(&env, [1_000i128, 2_000]);
+    let split_id = client.create_escrow(&params);
+    client.deposit(&split_id, &participant, &2_000);
+    client.deposit(&split_id, &p2, &1_000);
+
+    client.release_milestone(&creator, &split_id, &0);
+    client.cancel_split(&creator, &split_id);
+
+    // 2_000 remaining, split 2:1.
+    assert_eq!(
+        token_client.balance(&participant),
+        1_000_000 - 2_000 + 1_334
+    );
+    assert_eq!(token_client.balance(&p2), 1_000_000 - 1_000 + 666);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Cancelled);
+}
+
+#[test]
+fn test_release_funds_pays_remaining_milestones() {
+    let (env, client, admin, creator, participant, token_client, _ta) = setup();
+    client.set_treasury(&admin, &Address::generate(&env));
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 3_000);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Phased",
+        3_000,
+        &token_client.address,
+        &obligations,
+    );
+    params.milestones = Vec::from_array(&env, [1_000i128, 2_000]);
+    let split_id = client.create_escrow(&params);
+    client.deposit(&split_id, &participant, &3_000);
+
+    client.release_milestone(&creator, &split_id, &0);
+    client.release_funds(&creator, &split_id);
+    assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 1_000_000 + 3_000);
+
+    let mut bad = escrow_params(
+        &env,
+        &creator,
+        "Bad",
+        3_000,
+        &token_client.address,
+        &obligations,
+    );
+    bad.milestones = Vec::from_array(&env, [1_000i128, 1_000]);
+    let res = client.try_create_escrow(&bad);
+    assert_eq!(res, Err(Ok(crate::Error::InvalidMilestones)));
+}
+
+fn status_changes(env: &Env, contract: &Address) -> std::vec::Vec<(u64, SplitStatus, SplitStatus)> {
+    let topic = soroban_sdk::Symbol::new(env, "StatusChanged");
+    env.events()
+        .all()
+        .iter()
+        .filter(|e| {
+            e.0 == *contract
+                && soroban_sdk::Symbol::try_from_val(env, &e.1.get(0).unwrap())
+                    .map(|s| s == topic)
+                    .unwrap_or(false)
+        })
+        .map(|e| <(u64, SplitStatus, SplitStatus)>::from_val(env, &e.2))
+        .collect()
+}
+
+#[test]
+fn test_status_changed_event_on_every_transition() {
+    let (env, client, admin, creator, participant, token_client, _ta) = setup();
+    client.set_treasury(&admin, &Address::generate(&env));
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 3_000);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Lifecycle",
+        3_000,
+        &token_client.address,
+        &obligations,
+    );
+    params.milestones = Vec::from_array(&env, [1_000i128, 1_000, 1_000]);
+    let split_id = client.create_escrow(&params);
+
+    client.deposit(&split_id, &participant, &1_000);
+    assert_eq!(status_changes(&env, &client.address).len(), 0);
+
+    client.deposit(&split_id, &participant, &2_000);
+    client.release_milestone(&creator, &split_id, &0);
+    client.release_milestone(&creator, &split_id, &1);
+    client.release_milestone(&creator, &split_id, &2);
+
+    let changes = status_changes(&env, &client.address);
+    assert_eq!(
+        changes,
+        std::vec![
+            (split_id, SplitStatus::Pending, SplitStatus::Ready),
+            (split_id, SplitStatus::Ready, SplitStatus::PartiallyReleased),
+            (
+                split_id,
+                SplitStatus::PartiallyReleased,
+                SplitStatus::Released
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_paginated_split_queries_by_creator_participant_and_status() {
+    let (env, client, admin, creator, participant, token_client, token_admin) = setup();
+    client.set_treasury(&admin, &Address::generate(&env));
+    let other_creator = Address::generate(&env);
+    let p2 = Address::generate(&env);
+    token_admin.mint(&p2, &1_000_000);
+
+    let mut mine = Map::new(&env);
+    mine.set(participant.clone(), 100);
+    let mut theirs = Map::new(&env);
+    theirs.set(p2.clone(), 100);
+
+    let s1 = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "1",
+        100,
+        &token_client.address,
+        &mine,
+    ));
+    let s2 = client.create_escrow(&escrow_params(
+        &env,
+        &other_creator,
+        "2",
+        100,
+        &token_client.address,
+        &mine,
+    ));
+    let s3 = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "3",
+        100,
+        &token_client.address,
+        &theirs,
+    ));
+    let s4 = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "4",
+        100,
+        &token_client.address,
+        &mine,
+    ));
+
+    // Creator index, paged two at a time.
+    let page1 = client.get_splits_by_creator(&creator, &None, &0, &2);
+    assert_eq!(page1.split_ids, Vec::from_array(&env, [s1, s3]));
+    assert_eq!(page1.next, Some(2));
+    let page2 = client.get_splits_by_creator(&creator, &None, &2, &2);
+    assert_eq!(page2.split_ids, Vec::from_array(&env, [s4]));
+    assert_eq!(page2.next, None);
+    let page3 = client.get_splits_by_creator(&creator, &None, &3, &2);
+    assert!(page3.split_ids.is_empty());
+
+    // Participant index.
+    let ids = client.get_splits_by_participant(&participant, &None, &0, &10);
+    assert_eq!(ids.split_ids, Vec::from_array(&env, [s1, s2, s4]));
+
+    // Status index follows transitions.
+    client.deposit(&s1, &participant, &100);
+    client.deposit(&s3, &p2, &100);
+    client.release_funds(&creator, &s3);
+    client.cancel_split(&creator, &s4);
+
+    let by_status = |status: SplitStatus| client.get_splits_by_status(&status, &0, &10).split_ids;
+    assert_eq!(by_status(SplitStatus::Pending), Vec::from_array(&env, [s2]));
+    assert_eq!(by_status(SplitStatus::Ready), Vec::from_array(&env, [s1]));
+    assert_eq!(
+        by_status(SplitStatus::Released),
+        Vec::from_array(&env, [s3])
+    );
+    assert_eq!(
+        by_status(SplitStatus::Cancelled),
+        Vec::from_array(&env, [s4])
+    );
+
+    // "My open splits": participant filtered by status.
+    let open = client.get_splits_by_participant(&participant, &Some(SplitStatus::Pending), &0, &10);
+    assert_eq!(open.split_ids, Vec::from_array(&env, [s2]));
+    let creator_ready = client.get_splits_by_creator(&creator, &Some(SplitStatus::Ready), &0, &10);
+    assert_eq!(creator_ready.split_ids, Vec::from_array(&env, [s1]));
+
+    let res = client.try_get_splits_by_status(&SplitStatus::Pending, &0, &0);
+    assert_eq!(res, Err(Ok(crate::Error::InvalidInput)));
+}
+
+#[test]
+fn test_indexes_span_pages_and_stay_dense_after_removal() {
+    let (env, client, _admin, creator, participant, token_client, _) = setup();
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 100i128);
+    let count = crate::storage::INDEX_PAGE_SIZE + 10;
+    let mut created = std::vec::Vec::new();
+    for _ in 0..count {
+        // Each call must fit a fresh budget however large the indexes grow.
+        env.budget().reset_default();
+        created.push(client.create_escrow(&escrow_params(
+            &env,
+            &creator,
+            "Paged",
+            100,
+            &token_client.address,
+            &obligations,
+        )));
+    }
+    // Removing ids from the first page backfills them from the last one.
+    for split_id in &created[..5] {
+        env.budget().reset_default();
+        client.cancel_split(&creator, split_id);
+    }
+
+    let collect = |status: SplitStatus| {
+        let mut ids = std::vec::Vec::new();
+        let mut cursor = Some(0u32);
+        while let Some(at) = cursor {
+            let page = client.get_splits_by_status(&status, &at, &50);
+            ids.extend(page.split_ids.iter());
+            cursor = page.next;
+        }
+        ids.sort();
+        ids
+    };
+    assert_eq!(collect(SplitStatus::Pending), created[5..].to_vec());
+    assert_eq!(collect(SplitStatus::Cancelled), created[..5].to_vec());
+
+    let first = client.get_splits_by_participant(&participant, &None, &0, &50);
+    assert_eq!(first.split_ids.len(), 50);
+    assert_eq!(first.next, Some(50));
+    let rest = client.get_splits_by_participant(&participant, &None, &50, &50);
+    assert_eq!(rest.split_ids.len(), 10);
+    assert_eq!(rest.next, None);
+}
+
+fn last_fee_applied(env: &Env, contract: &Address) -> (i128, AppliedFee) {
+    let topic = soroban_sdk::Symbol::new(env, "FeesCollected");
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .filter(|e| {
+            e.0 == *contract
+                && soroban_sdk::Symbol::try_from_val(env, &e.1.get(0).unwrap())
+                    .map(|s| s == topic)
+                    .unwrap_or(false)
+        })
+        .last()
+        .unwrap();
+    let (amount, _treasury, applied) = <(i128, Address, AppliedFee)>::from_val(env, &event.2);
+    (amount, applied)
+}
+
+fn fee_tier(min_amount: i128, fee_bps: u32) -> FeeTier {
+    FeeTier {
+        min_amount,
+        fee_bps,
+    }
+}
+
+#[test]
+fn test_tiered_fee_schedule_with_bounds_and_creator_override() {
+    let (env, client, admin, creator, participant, token_client, _ta) = setup();
+    let treasury = Address::generate(&env);
+    client.set_treasury(&admin, &treasury);
+    client.set_fee(&admin, &9_999u32); // ignored once tiers exist
+    client.set_fee_schedule(
+        &admin,
+        &FeeSchedule {
+            tiers: Vec::from_array(&env, [fee_tier(0, 300), fee_tier(5_000, 100)]),
+            min_fee: 5,
+            max_fee: Some(40),
+        },
+    );
+
+    let release = |amount: i128, who: &Address| {
+        let mut obligations = Map::new(&env);
+        obligations.set(participant.clone(), amount);
+        let id = client.create_escrow(&escrow_params(
+            &env,
+            who,
+            "Fee",
+            amount,
+            &token_client.address,
+            &obligations,
+        ));
+        client.deposit(&id, &participant, &amount);
+        client.release_funds(who, &id);
+        last_fee_applied(&env, &client.address)
+    };
+
+    // 3% of 1_000.
+    assert_eq!(release(1_000, &creator), (30, AppliedFee::Tier(0)));
+    // 1% of 10_000 = 100, capped at 40.
+    assert_eq!(release(10_000, &creator), (40, AppliedFee::Tier(1)));
+    // 3% of 100 = 3, raised to the 5 minimum.
+    assert_eq!(release(100, &creator), (5, AppliedFee::Tier(0)));
+    assert_eq!(token_client.balance(&treasury), 75);
+
+    // A creator override bypasses tiers and bounds.
+    client.set_creator_fee(&admin, &creator, &Some(0u32));
+    assert_eq!(release(1_000, &creator), (0, AppliedFee::CreatorOverride));
+    client.set_creator_fee(&admin, &creator, &None);
+    assert_eq!(release(1_000, &creator), (30, AppliedFee::Tier(0)));
+}
+
+#[test]
+fn test_fee_schedule_validation() {
+    let (env, client, admin, _creator, _participant, _tc, _ta) = setup();
+
+    let bad = [
+        // Thresholds must strictly increase.
+        FeeSchedule {
+            tiers: Vec::from_array(
+                &env,
+                [fee_tier(0, 300), fee_tier(5_000, 200), fee_tier(5_000, 100)],
+            ),
+            min_fee: 0,
+            max_fee: None,
+        },
+        // First bracket must start at zero.
+        FeeSchedule {
+            tiers: Vec::from_array(&env, [fee_tier(100, 300)]),
+            min_fee: 0,
+            max_fee: None,
+        },
+        // max_fee below min_fee.
+        FeeSchedule {
+            tiers: Vec::new(&env),
+            min_fee: 10,
+            max_fee: Some(5),
+        },
+    ];
+    for schedule in bad {
+        let res = client.try_set_fee_schedule(&admin, &schedule);
+        assert_eq!(res, Err(Ok(crate::Error::InvalidFeeSchedule)));
+    }
+
+    let res = client.try_set_fee_schedule(
+        &admin,
+        &FeeSchedule {
+            tiers: Vec::from_array(&env, [fee_tier(0, 10_001)]),
+            min_fee: 0,
+            max_fee: None,
+        },
+    );
+    assert_eq!(res, Err(Ok(crate::Error::InvalidFeeBps)));
+    assert_eq!(client.get_fee_schedule(), None);
+}
+
+#[test]
+fn test_migrate_rewrites_v1_splits_in_batches() {
+    use crate::migration::SplitV1;
+    use crate::storage::DataKey;
+
+    let (env, client, admin, creator, participant, token_client, _ta) = setup();
+    client.set_treasury(&admin, &Address::generate(&env));
+
+    // Simulate a deployment whose data predates schema 2.
+    let legacy = |split_id: u64, status: SplitStatus, deposited: i128| {
+        let mut obligations = Map::new(&env);
+        obligations.set(participant.clone(), 100i128);
+        let mut balances = Map::new(&env);
+        let mut participants = Vec::new(&env);
+        if deposited > 0 {
+            balances.set(participant.clone(), deposited);
+            participants.push_back(participant.clone());
+        }
+        SplitV1 {
+            split_id,
+            creator: creator.clone(),
+            description: String::from_str(&env, "legacy"),
+            metadata: Map::new(&env),
+            total_amount: 100,
+            deposited_amount: deposited,
+            status,
+            max_participants: 50,
+            participants,
+            balances,
+            obligations,
+            note: String::from_str(&env, ""),
+        }
+    };
+    env.as_contract(&client.address, || {
+        let store = env.storage().persistent();
+        store.set(&DataKey::Split(1), &legacy(1, SplitStatus::Pending, 0));
+        store.set(&DataKey::Split(2), &legacy(2, SplitStatus::Ready, 100));
+        store.set(&DataKey::Split(3), &legacy(3, SplitStatus::Released, 100));
+        let instance = env.storage().instance();
+        instance.set(&DataKey::Token, &token_client.address);
+        instance.set(&DataKey::NextSplitId, &4u64);
+        instance.set(&DataKey::SchemaVersion, &1u32);
+    });
+    token_client.transfer(&participant, &client.address, &100);
+
+    assert_eq!(client.get_schema_version(), 1);
+    let res = client.try_upgrade(
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        &String::from_str(&env, "2.0.0"),
+    );
+    assert_eq!(res, Err(Ok(crate::Error::MigrationPending)));
+
+    assert!(!client.migrate(&2));
+    assert_eq!(client.get_escrow(&1).token, token_client.address);
+    assert!(client.migrate(&2));
+    assert_eq!(client.get_schema_version(), 2);
+    // Idempotent once complete.
+    assert!(client.migrate(&2));
+
+    let released = client.get_escrow(&3);
+    assert_eq!(released.released_amount, 100);
+    assert_eq!(released.deadline, None);
+    assert_eq!(
+        client
+            .get_splits_by_creator(&creator, &None, &0, &10)
+            .split_ids,
+        Vec::from_array(&env, [1u64, 2, 3])
+    );
+
+    // Migrated splits are fully usable.
+    client.release_funds(&creator, &2);
+    assert_eq!(token_client.balance(&creator), 1_000_000 + 100);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_upgrade_non_admin_fails() {
+    let (env, client, _, creator, _, _, _) = setup();
+    let hash = soroban_sdk::BytesN::from_array(&env, &[1u8; 32]);
+    let version = String::from_str(&env, "2.0.0");
+
+    let mut args = Vec::new(&env);
+    args.push_back(hash.clone().into_val(&env));
+    args.push_back(version.clone().into_val(&env));
+    env.mock_auths(&[soroban_sdk::testutils::MockAuth {
+        address: &creator,
+        invoke: &soroban_sdk::testutils::MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "upgrade",
+            args,
+            sub_invokes: &[],
+        },
+    }]);
+
+    client.upgrade(&hash, &version);
+}
+
+#[test]
+fn test_admin_transfer_is_two_step() {
+    let (env, client, admin, _, _, _, _) = setup();
+    let new_admin = Address::generate(&env);
+
+    assert_eq!(
+        client.try_accept_admin(),
+        Err(Ok(crate::Error::NoPendingAdmin))
+    );
+
+    client.propose_admin(&new_admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    // Nothing changes until the nominee accepts.
+    assert_eq!(client.get_admin(), admin);
+
+    client.accept_admin();
+    assert_eq!(
+        env.auths()[0].0,
+        new_admin,
+        "accept_admin must be authorized by the nominee"
+    );
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+
+    // The old admin lost its implicit fee permissions.
+    assert_eq!(
+        client.try_set_fee(&admin, &10u32),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+    client.set_fee(&new_admin, &10u32);
+}
+
+#[test]
+fn test_fee_manager_role_grant_and_revoke() {
+    let (env, client, _admin, creator, _, _, _) = setup();
+    let manager = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_treasury(&manager, &creator),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+
+    let before = env.events().all().len();
+    client.grant_role(&Role::FeeManager, &manager);
+    assert!(client.has_role(&Role::FeeManager, &manager));
+    assert!(!client.has_role(&Role::Pauser, &manager));
+    assert_eq!(env.events().all().len(), before + 1);
+
+    client.set_treasury(&manager, &creator);
+    client.set_fee(&manager, &50u32);
+    client.set_creator_fee(&manager, &creator, &Some(0u32));
+
+    client.revoke_role(&Role::FeeManager, &manager);
+    assert!(!client.has_role(&Role::FeeManager, &manager));
+    assert_eq!(
+        client.try_set_fee(&manager, &60u32),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+
+    // Holding a different role does not grant fee permissions.
+    client.grant_role(&Role::Pauser, &manager);
+    assert_eq!(
+        client.try_set_fee(&manager, &60u32),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_pause_blocks_state_changes_but_not_cancel() {
+    let (env, client, admin, creator, participant, token_client, _) = setup();
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_000i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Paused",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+    client.deposit(&split_id, &participant, &400);
+
+    let pauser = Address::generate(&env);
+    assert_eq!(
+        client.try_set_paused(&pauser, &true),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+    client.grant_role(&Role::Pauser, &pauser);
+    client.set_paused(&pauser, &true);
+    assert!(client.is_paused());
+
+    assert_eq!(
+        client.try_deposit(&split_id, &participant, &100),
+        Err(Ok(crate::Error::Paused))
+    );
+    assert_eq!(
+        client.try_create_escrow(&escrow_params(
+            &env,
+            &creator,
+            "Blocked",
+            1_000,
+            &token_client.address,
+            &obligations,
+        )),
+        Err(Ok(crate::Error::Paused))
+    );
+    assert_eq!(
+        client.try_release_funds(&creator, &split_id),
+        Err(Ok(crate::Error::Paused))
+    );
+
+    // Users can always get their money back.
+    client.cancel_split(&creator, &split_id);
+    assert_eq!(token_client.balance(&participant), 1_000_000);
+
+    client.set_paused(&admin, &false);
+    client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Resumed",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+}
+
+#[test]
+fn test_frozen_split_is_isolated() {
+    let (env, client, admin, creator, participant, token_client, _) = setup();
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_000i128);
+    let frozen = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Frozen",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+    let other = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Other",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+
+    client.set_split_frozen(&admin, &frozen, &true);
+    assert!(client.is_split_frozen(&frozen));
+    assert_eq!(
+        client.try_deposit(&frozen, &participant, &1_000),
+        Err(Ok(crate::Error::Paused))
+    );
+    client.deposit(&other, &participant, &1_000);
+
+    client.set_split_frozen(&admin, &frozen, &false);
+    client.deposit(&frozen, &participant, &1_000);
+    assert_eq!(
+        client.try_set_split_frozen(&admin, &99, &true),
+        Err(Ok(crate::Error::SplitNotFound))
+    );
+}
+
+#[test]
+fn test_recurring_cycles_roll_on_schedule() {
+    let (env, client, _admin, creator, participant, token_client, _) = setup();
+    env.ledger().set_timestamp(10_000);
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 500i128);
+    let recurring_id = client.create_recurring(
+        &creator,
+        &String::from_str(&env, "Rent"),
+        &token_client.address,
+        &500,
+        &obligations,
+        &100,
+        &3,
+    );
+
+    let first = client.roll_cycle(&recurring_id);
+    assert_eq!(
+        client.try_roll_cycle(&recurring_id),
+        Err(Ok(crate::Error::CycleNotDue))
+    );
+
+    env.ledger().set_timestamp(10_100);
+    let second = client.roll_cycle(&recurring_id);
+    assert_eq!(
+        client.get_cycle_link(&second),
+        Some(crate::CycleLink {
+            recurring_id,
+            cycle: 1,
+            previous: Some(first),
+        })
+    );
+    let split = client.get_escrow(&second);
+    assert_eq!(split.total_amount, 500);
+    assert_eq!(split.obligations.get(participant.clone()), Some(500));
+    assert_eq!(split.status, SplitStatus::Pending);
+
+    // Paused schedules do not roll; resuming re-anchors an overdue schedule to now.
+    client.set_recurring_paused(&recurring_id, &true);
+    env.ledger().set_timestamp(10_500);
+    assert_eq!(
+        client.try_roll_cycle(&recurring_id),
+        Err(Ok(crate::Error::Paused))
+    );
+    client.set_recurring_paused(&recurring_id, &false);
+    assert_eq!(client.get_recurring(&recurring_id).next_cycle_at, 10_500);
+    let third = client.roll_cycle(&recurring_id);
+
+    assert_eq!(
+        client.get_cycle_history(&recurring_id),
+        Vec::from_array(&env, [first, second, third])
+    );
+    env.ledger().set_timestamp(20_000);
+    assert_eq!(
+        client.try_roll_cycle(&recurring_id),
+        Err(Ok(crate::Error::CyclesExhausted))
+    );
+    assert_eq!(client.get_cycle_link(&1_000), None);
+}
+
+#[test]
+fn test_recurring_rejects_invalid_schedule() {
+    let (env, client, _admin, creator, participant, token_client, _) = setup();
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 500i128);
+    let description = String::from_str(&env, "Bad");
+
+    let res = client.try_create_recurring(
+        &creator,
+        &description,
+        &token_client.address,
+        &500,
+        &obligations,
+        &0,
+        &3,
+    );
+    assert_eq!(res, Err(Ok(crate::Error::InvalidInput)));
+    let res = client.try_create_recurring(
+        &creator,
+        &description,
+        &token_client.address,
+        &600,
+        &obligations,
+        &100,
+        &3,
+    );
+    assert_eq!(res, Err(Ok(crate::Error::TotalAmountMismatch)));
+    assert_eq!(
+        client.try_roll_cycle(&7),
+        Err(Ok(crate::Error::RecurringNotFound))
+    );
+}
+
+#[test]
+fn test_collect_from_pulls_preapproved_deposit() {
+    let (env, client, _admin, creator, participant, token_client, _) = setup();
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_000i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Subscription",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+    client.deposit(&split_id, &participant, &300);
+
+    let expiration = env.ledger().sequence() + 100;
+    token_client.approve(&participant, &client.address, &500, &expiration);
+    assert_eq!(
+        client.try_collect_from(&creator, &split_id, &participant),
+        Err(Ok(crate::Error::InsufficientAllowance))
+    );
+
+    token_client.approve(&participant, &client.address, &700, &expiration);
+    assert_eq!(client.collect_from(&creator, &split_id, &participant), 700);
+    assert_eq!(token_client.balance(&client.address), 1_000);
+    assert_eq!(token_client.allowance(&participant, &client.address), 0);
+    assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Ready);
+
+    // Nothing left to collect.
+    assert_eq!(
+        client.try_collect_from(&creator, &split_id, &participant),
+        Err(Ok(crate::Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_collect_from_requires_creator_or_keeper() {
+    let (env, client, _admin, creator, participant, token_client, _) = setup();
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_000i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Keeper",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+    let expiration = env.ledger().sequence() + 100;
+    token_client.approve(&participant, &client.address, &1_000, &expiration);
+
+    let keeper = Address::generate(&env);
+    assert_eq!(
+        client.try_collect_from(&keeper, &split_id, &participant),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_collect_from(&keeper, &split_id, &Address::generate(&env)),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+
+    client.grant_role(&Role::Keeper, &keeper);
+    assert_eq!(client.collect_from(&keeper, &split_id, &participant), 1_000);
+    assert_eq!(token_client.balance(&participant), 1_000_000 - 1_000);
+}
+
+#[test]
+fn test_amendment_applies_after_all_affected_approve() {
+    let (env, client, _admin, creator, p1, token_client, token_admin) = setup();
+    let p2 = Address::generate(&env);
+    let p3 = Address::generate(&env);
+    token_admin.mint(&p2, &1_000);
+    token_admin.mint(&p3, &1_000);
+    let mut obligations = Map::new(&env);
+    obligations.set(p1.clone(), 600i128);
+    obligations.set(p2.clone(), 400i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Amend",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+    client.deposit(&split_id, &p1, &600);
+    client.deposit(&split_id, &p2, &100);
+
+    // p1 drops to 250, p2 is unchanged, p3 joins; total grows to 750.
+    let mut amended = Map::new(&env);
+    amended.set(p1.clone(), 250i128);
+    amended.set(p2.clone(), 400i128);
+    amended.set(p3.clone(), 100i128);
+    let res = client.try_propose_amendment(&split_id, &amended, &1_000);
+    assert_eq!(res, Err(Ok(crate::Error::TotalAmountMismatch)));
+    client.propose_amendment(&split_id, &amended, &750);
+    assert_eq!(
+        client.get_amendment(&split_id).unwrap().affected,
+        Vec::from_array(&env, [p1.clone(), p3.clone()])
+    );
+
+    assert_eq!(
+        client.try_approve_amendment(&split_id, &p2),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+    assert!(!client.approve_amendment(&split_id, &p1));
+    assert_eq!(client.get_escrow(&split_id).total_amount, 1_000);
+    assert!(client.approve_amendment(&split_id, &p3));
+
+    let split = client.get_escrow(&split_id);
+    assert_eq!(split.total_amount, 750);
+    assert_eq!(split.obligations, amended);
+    assert_eq!(split.balances.get(p1.clone()), Some(250));
+    assert_eq!(split.deposited_amount, 350);
+    assert_eq!(token_client.balance(&p1), 1_000_000 - 250);
+    assert_eq!(token_client.balance(&client.address), 350);
+    assert!(client.get_amendment(&split_id).is_none());
+    assert_eq!(
+        client
+            .get_splits_by_participant(&p3, &None, &0, &10)
+            .split_ids,
+        Vec::from_array(&env, [split_id])
+    );
+
+    client.deposit(&split_id, &p2, &300);
+    client.deposit(&split_id, &p3, &100);
+    assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Ready);
+}
+
+#[test]
+fn test_amendment_removing_participant_refunds_and_unindexes() {
+    let (env, client, _admin, creator, p1, token_client, token_admin) = setup();
+    let p2 = Address::generate(&env);
+    token_admin.mint(&p2, &1_000);
+    let mut obligations = Map::new(&env);
+    obligations.set(p1.clone(), 500i128);
+    obligations.set(p2.clone(), 500i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Drop",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+    client.deposit(&split_id, &p1, &500);
+    client.deposit(&split_id, &p2, &200);
+
+    let mut amended = Map::new(&env);
+    amended.set(p1.clone(), 500i128);
+    client.propose_amendment(&split_id, &amended, &500);
+    assert!(client.approve_amendment(&split_id, &p2));
+
+    let split = client.get_escrow(&split_id);
+    assert_eq!(split.status, SplitStatus::Ready);
+    assert_eq!(split.participants, Vec::from_array(&env, [p1.clone()]));
+    assert_eq!(token_client.balance(&p2), 1_000);
+    assert_eq!(
+        client
+            .get_splits_by_participant(&p2, &None, &0, &10)
+            .split_ids,
+        Vec::new(&env)
+    );
+    assert_eq!(
+        client.try_approve_amendment(&split_id, &p2),
+        Err(Ok(crate::Error::AmendmentNotFound))
+    );
+}
+
+#[test]
+fn test_deposit_with_intent_relayed_by_sponsor() {
+    let (env, client, _admin, creator, participant, token_client, _) = setup();
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_000i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Gasless",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+    env.ledger().set_timestamp(1_000);
+    let relayer = Address::generate(&env);
+    let intent = crate::DepositIntent {
+        split_id,
+        amount: 410,
+        nonce: 0,
+        expiry: 2_000,
+        relayer_fee: 10,
+        relayer: relayer.clone(),
+    };
+
+    client.deposit_with_intent(&participant, &intent);
+    // The participant authorized exactly this intent; the transfers are nested under it.
+    let (signer, invocation) = env.auths()[0].clone();
+    assert_eq!(signer, participant);
+    assert_eq!(
+        invocation.function,
+        soroban_sdk::testutils::AuthorizedFunction::Contract((
+            client.address.clone(),
+            soroban_sdk::Symbol::new(&env, "deposit_with_intent"),
+            (intent.clone(),).into_val(&env),
+        ))
+    );
+    assert_eq!(invocation.sub_invocations.len(), 2);
+    assert_eq!(token_client.balance(&relayer), 10);
+    assert_eq!(
+        client
+            .get_escrow(&split_id)
+            .balances
+            .get(participant.clone()),
+        Some(400)
+    );
+    assert_eq!(client.get_intent_nonce(&participant), 1);
+
+    // The signed intent fixes the fee recipient; naming another relayer needs a new
+    // signature from the participant.
+    let hijacked = crate::DepositIntent {
+        nonce: 1,
+        relayer: Address::generate(&env),
+        ..intent.clone()
+    };
+    env.mock_auths(&[soroban_sdk::testutils::MockAuth {
+        address: &participant,
+        invoke: &soroban_sdk::testutils::MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "deposit_with_intent",
+            args: (crate::DepositIntent {
+                nonce: 1,
+                ..intent.clone()
+            },)
+                .into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client
+        .try_deposit_with_intent(&participant, &hijacked)
+        .is_err());
+    env.mock_all_auths();
+
+    // Replaying the same intent fails.
+    assert_eq!(
+        client.try_deposit_with_intent(&participant, &intent),
+        Err(Ok(crate::Error::InvalidNonce))
+    );
+
+    let late = crate::DepositIntent {
+        nonce: 1,
+        expiry: 999,
+        ..intent
+    };
+    assert_eq!(
+        client.try_deposit_with_intent(&participant, &late),
+        Err(Ok(crate::Error::IntentExpired))
+    );
+}
+
+#[test]
+fn test_disputed_split_only_leaves_through_dispute_contract() {
+    let (env, client, _admin, creator, participant, token_client, _) = setup();
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_000i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Disputed",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+    client.deposit(&split_id, &participant, &1_000);
+
+    assert_eq!(
+        client.try_hold_for_dispute(&participant, &split_id),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+    let dispute_contract = Address::generate(&env);
+    client.set_dispute_contract(&dispute_contract);
+    // Only the creator or an obligated participant can have a split held.
+    assert_eq!(
+        client.try_hold_for_dispute(&Address::generate(&env), &split_id),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+    client.hold_for_dispute(&participant, &split_id);
+    assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Disputed);
+    assert_eq!(
+        client.try_hold_for_dispute(&creator, &split_id),
+        Err(Ok(crate::Error::InvalidInput))
+    );
+
+    // The creator alone can neither release nor cancel.
+    for fn_name in ["release_funds", "cancel_split"] {
+        env.mock_auths(&[soroban_sdk::testutils::MockAuth {
+            address: &creator,
+            invoke: &soroban_sdk::testutils::MockAuthInvoke {
+                contract: &client.address,
+                fn_name,
+                args: (creator.clone(), split_id).into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        let res = if fn_name == "release_funds" {
+            client.try_release_funds(&creator, &split_id)
+        } else {
+            client.try_cancel_split(&creator, &split_id)
+        };
+        assert!(res.is_err());
+    }
+
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_release_milestone(&creator, &split_id, &0),
+        Err(Ok(crate::Error::SplitNotReady))
+    );
+
+    // The dispute contract's callback settles it.
+    client.reverse_split(&creator, &split_id);
+    assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Cancelled);
+    assert_eq!(client.get_dispute_hold(&split_id), None);
+    assert_eq!(token_client.balance(&participant), 1_000_000);
+}
+
+#[test]
+fn test_lifted_dispute_hold_restores_pre_dispute_status() {
+    let (env, client, admin, creator, participant, token_client, _) = setup();
+    client.set_treasury(&admin, &Address::generate(&env));
+    client.set_dispute_contract(&Address::generate(&env));
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_000i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Underfunded",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+    client.deposit(&split_id, &participant, &400);
+
+    // A dismissed dispute on a split still collecting deposits must not pay out.
+    client.hold_for_dispute(&participant, &split_id);
+    assert_eq!(
+        client.try_release_funds(&creator, &split_id),
+        Err(Ok(crate::Error::SplitNotReady))
+    );
+    client.lift_dispute_hold(&split_id);
+    let split = client.get_escrow(&split_id);
+    assert_eq!(split.status, SplitStatus::Pending);
+    assert_eq!(split.released_amount, 0);
+    assert_eq!(token_client.balance(&creator), 1_000_000);
+    assert_eq!(client.get_dispute_hold(&split_id), None);
+    assert_eq!(
+        client.try_lift_dispute_hold(&split_id),
+        Err(Ok(crate::Error::SplitNotActive))
+    );
+
+    // Deposits resume and the split settles normally.
+    client.deposit(&split_id, &participant, &600);
+    client.hold_for_dispute(&creator, &split_id);
+    client.lift_dispute_hold(&split_id);
+    assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Ready);
+    client.release_funds(&creator, &split_id);
+    assert_eq!(token_client.balance(&creator), 1_000_000 + 1_000);
+}
+
+#[test]
+fn test_unresolved_dispute_hold_can_only_be_cleared_by_admin_after_timeout() {
+    let (env, client, admin, creator, participant, token_client, _) = setup();
+    let dispute_contract = Address::generate(&env);
+    client.set_dispute_contract(&dispute_contract);
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_000i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Abandoned dispute",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+    client.deposit(&split_id, &participant, &1_000);
+    env.ledger().set_timestamp(1_000);
+    client.hold_for_dispute(&participant, &split_id);
+
+    assert_eq!(
+        client.try_clear_stale_dispute_hold(&split_id),
+        Err(Ok(crate::Error::DeadlineNotReached))
+    );
+    env.ledger()
+        .set_timestamp(1_000 + crate::disputes::HOLD_TIMEOUT);
+
+    // Even after the timeout, nobody but the dispute contract or the admin may lift it.
+    env.mock_auths(&[]);
+    assert!(client.try_lift_dispute_hold(&split_id).is_err());
+    assert!(client.try_clear_stale_dispute_hold(&split_id).is_err());
+    assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Disputed);
+
+    env.mock_auths(&[soroban_sdk::testutils::MockAuth {
+        address: &admin,
+        invoke: &soroban_sdk::testutils::MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "clear_stale_dispute_hold",
+            args: (split_id,).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert_eq!(
+        client.clear_stale_dispute_hold(&split_id),
+        SplitStatus::Ready
+    );
+    assert_eq!(client.get_dispute_hold(&split_id), None);
+}
+
+#[test]
+fn test_settlement_receipt_after_release() {
+    use soroban_sdk::xdr::ToXdr;
+
+    let (env, client, admin, creator, p1, token_client, token_admin) = setup();
+    let p2 = Address::generate(&env);
+    token_admin.mint(&p2, &1_000);
+    let treasury = Address::generate(&env);
+    client.set_treasury(&admin, &treasury);
+    client.set_fee(&admin, &100u32); // 1%
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let mut obligations = Map::new(&env);
+    obligations.set(p1.clone(), 600i128);
+    obligations.set(p2.clone(), 400i128);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Receipt",
+        1_000,
+        &token_client.address,
+        &obligations,
+    );
+    params.payouts = Vec::from_array(
+        &env,
+        [
+            payout(&alice, PayoutShare::Bps(5_000)),
+            payout(&bob, PayoutShare::Bps(5_000)),
+        ],
+    );
+
+    env.ledger().set_sequence_number(10);
+    env.ledger().set_timestamp(1_000);
+    let split_id = client.create_escrow(&params);
+    client.deposit(&split_id, &p1, &600);
+    env.ledger().set_sequence_number(20);
+    env.ledger().set_timestamp(2_000);
+    client.deposit(&split_id, &p2, &400);
+    env.ledger().set_sequence_number(30);
+    env.ledger().set_timestamp(3_000);
+    client.release_funds(&creator, &split_id);
+
+    let receipt = client.get_settlement_receipt(&split_id);
+    let summary = receipt.summary.clone();
+    assert_eq!(summary.status, SplitStatus::Released);
+    assert_eq!(summary.total_amount, 1_000);
+    assert_eq!(summary.fee, 10);
+    assert_eq!(summary.treasury, Some(treasury));
+    assert_eq!(summary.payouts.get(alice), Some(495));
+    assert_eq!(summary.payouts.get(bob), Some(495));
+    assert_eq!(summary.lines.len(), 2);
+    for line in summary.lines.iter() {
+        assert_eq!(line.paid, line.obligation);
+        assert_eq!(line.refunded, 0);
+    }
+    assert_eq!(
+        summary.history,
+        Vec::from_array(
+            &env,
+            [
+                crate::StatusStamp {
+                    status: SplitStatus::Pending,
+                    ledger: 10,
+                    timestamp: 1_000,
+                },
+                crate::StatusStamp {
+                    status: SplitStatus::Ready,
+                    ledger: 20,
+                    timestamp: 2_000,
+                },
+                crate::StatusStamp {
+                    status: SplitStatus::Released,
+                    ledger: 30,
+                    timestamp: 3_000,
+                },
+            ]
+        )
+    );
+
+    // The commitment can be recomputed from the summary alone.
+    let expected: soroban_sdk::BytesN<32> = env.crypto().sha256(&summary.to_xdr(&env)).into();
+    assert_eq!(receipt.commitment, expected);
+    assert_eq!(
+        client.try_get_settlement_receipt(&99),
+        Err(Ok(crate::Error::SplitNotFound))
+    );
+}
+
+#[test]
+fn test_settlement_receipt_keeps_deposits_after_refund() {
+    let (env, client, _admin, creator, p1, token_client, _) = setup();
+    let p2 = Address::generate(&env);
+    env.ledger().set_timestamp(1_000);
+    let mut obligations = Map::new(&env);
+    obligations.set(p1.clone(), 500i128);
+    obligations.set(p2.clone(), 500i128);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Refunded",
+        1_000,
+        &token_client.address,
+        &obligations,
+    );
+    params.deadline = Some(2_000);
+    let cancelled = client.create_escrow(&params);
+    let expired = client.create_escrow(&params);
+    client.deposit(&cancelled, &p1, &300);
+    client.deposit(&expired, &p1, &200);
+
+    client.cancel_split(&creator, &cancelled);
+    env.ledger().set_timestamp(2_001);
+    client.expire_split(&expired);
+
+    for (split_id, deposited) in [(cancelled, 300), (expired, 200)] {
+        let lines = client.get_settlement_receipt(&split_id).summary.lines;
+        let line = |participant: &Address| {
+            lines
+                .iter()
+                .find(|line| line.participant == *participant)
+                .unwrap()
+        };
+        assert_eq!((line(&p1).paid, line(&p1).refunded), (deposited, deposited));
+        assert_eq!((line(&p2).paid, line(&p2).refunded), (0, 0));
+    }
+}
+
+#[test]
+fn test_ttl_policy_extends_on_read_and_on_demand() {
+    use soroban_sdk::testutils::storage::{Instance as _, Persistent as _};
+
+    let (env, client, _admin, creator, participant, token_client, _) = setup();
+    let split_ttl = |env: &Env, split_id: u64| {
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .get_ttl(&crate::storage::DataKey::Split(split_id))
+        })
+    };
+
+    assert_eq!(
+        client.try_set_ttl_policy(&crate::TtlPolicy {
+            threshold: 5_000,
+            extend_to: 5_000,
+        }),
+        Err(Ok(crate::Error::InvalidInput))
+    );
+    let policy = crate::TtlPolicy {
+        threshold: 1_000,
+        extend_to: 5_000,
+    };
+    client.set_ttl_policy(&policy);
+    assert_eq!(client.get_ttl_policy(), policy);
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_000i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Long lived",
+        1_000,
+        &token_client.address,
+        &obligations,
+    ));
+    let initial = split_ttl(&env, split_id);
+
+    // A plain read extends an entry that has dropped below the threshold.
+    let start = env.ledger().sequence();
+    env.ledger().set_sequence_number(start + initial - 500);
+    client.get_escrow(&split_id);
+    assert_eq!(split_ttl(&env, split_id), 5_000);
+    // Every entry point keeps the instance above the threshold too.
+    assert!(env.as_contract(&client.address, || env.storage().instance().get_ttl()) >= 1_000);
+
+    // Anyone can top a split and its side entries up to the full TTL.
+    env.ledger().set_sequence_number(start + initial - 100);
+    client.extend_split_ttl(&split_id);
+    assert_eq!(split_ttl(&env, split_id), 5_000);
+    assert_eq!(
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .get_ttl(&crate::storage::DataKey::SettlementLog(split_id))
+        }),
+        5_000
+    );
+    assert_eq!(
+        client.try_extend_split_ttl(&99),
+        Err(Ok(crate::Error::SplitNotFound))
+    );
+}
+
+#[test]
+fn test_admin_limits_apply_to_new_splits() {
+    let (env, client, _admin, creator, participant, token_client, _) = setup();
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 1_000i128);
+    let long_note = String::from_str(&env, &"n".repeat(200));
+
+    let defaults = client.get_limits();
+    assert_eq!(defaults.max_participants, 50);
+    assert_eq!(defaults.max_note_len, 128);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Enterprise",
+        1_000,
+        &token_client.address,
+        &obligations,
+    );
+    params.note = Some(long_note.clone());
+    assert_eq!(
+        client.try_create_escrow(&params),
+        Err(Ok(crate::Error::InvalidInput))
+    );
+
+    let enterprise = crate::Limits {
+        max_participants: 80,
+        max_note_len: 256,
+        max_metadata_entries: 2,
+        max_metadata_string_len: 128,
+    };
+    let before = env.events().all().len();
+    client.set_limits(&enterprise);
+    assert_eq!(env.events().all().len(), before + 1);
+    assert_eq!(client.get_limits(), enterprise);
+
+    let split_id = client.create_escrow(&params);
+    let split = client.get_escrow(&split_id);
+    assert_eq!(split.max_participants, 80);
+    assert_eq!(split.note, long_note);
+
+    let too_many = metadata_map(&env, &[("a", "1"), ("b", "2"), ("c", "3")]);
+    assert_eq!(
+        client.try_update_metadata(&split_id, &too_many),
+        Err(Ok(crate::Error::InvalidMetadata))
+    );
+
+    for limits in [
+        crate::Limits {
+            max_participants: 0,
+            ..enterprise.clone()
+        },
+        crate::Limits {
+            max_note_len: 1_025,
+            ..enterprise.clone()
+        },
+    ] {
+        assert_eq!(
+            client.try_set_limits(&limits),
+            Err(Ok(crate::Error::InvalidInput))
+        );
+    }
+}
+
+#[test]
+fn test_split_at_every_limit_bound_fits_one_entry_and_one_transaction() {
+    use crate::limits::{
+        MAX_METADATA_ENTRIES_BOUND, MAX_METADATA_STRING_LEN_BOUND, MAX_NOTE_LEN_BOUND,
+        MAX_PARTICIPANTS_BOUND,
+    };
+    use soroban_sdk::xdr::ToXdr;
+
+    let (env, client, _admin, creator, _participant, token_client, token_admin) = setup();
+    client.set_limits(&crate::Limits {
+        max_participants: MAX_PARTICIPANTS_BOUND,
+        max_note_len: MAX_NOTE_LEN_BOUND,
+        max_metadata_entries: MAX_METADATA_ENTRIES_BOUND,
+        max_metadata_string_len: MAX_METADATA_STRING_LEN_BOUND,
+    });
+
+    let len = MAX_METADATA_STRING_LEN_BOUND as usize;
+    let mut metadata = Map::new(&env);
+    for i in 0..MAX_METADATA_ENTRIES_BOUND {
+        metadata.set(
+            String::from_str(&env, &std::format!("{i:0>len$}")),
+            String::from_str(&env, &"v".repeat(len)),
+        );
+    }
+    let mut participants = std::vec::Vec::new();
+    let mut obligations = Map::new(&env);
+    for _ in 0..MAX_PARTICIPANTS_BOUND {
+        let participant = Address::generate(&env);
+        token_admin.mint(&participant, &1);
+        obligations.set(participant.clone(), 1i128);
+        participants.push(participant);
+    }
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Maxed out",
+        MAX_PARTICIPANTS_BOUND as i128,
+        &token_client.address,
+        &obligations,
+    );
+    params.metadata = metadata;
+    params.note = Some(String::from_str(
+        &env,
+        &"n".repeat(MAX_NOTE_LEN_BOUND as usize),
+    ));
+    env.budget().reset_default();
+    let split_id = client.create_escrow(&params);
+    for participant in participants.iter() {
+        env.budget().reset_default();
+        client.deposit(&split_id, participant, &1);
+    }
+
+    let split = client.get_escrow(&split_id);
+    assert_eq!(split.status, SplitStatus::Ready);
+    assert_eq!(split.participants.len(), MAX_PARTICIPANTS_BOUND);
+    assert!(split.to_xdr(&env).len() < 64 * 1024);
+
+    // Refunding every participant also fits one transaction.
+    env.budget().reset_default();
+    client.cancel_split(&creator, &split_id);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_deposit_many_is_all_or_nothing() {
+    use crate::BatchOutcome;
+
+    let (env, client, _admin, creator, participant, token_client, _) = setup();
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 500i128);
+    let mut ids = std::vec::Vec::new();
+    for _ in 0..3 {
+        ids.push(client.create_escrow(&escrow_params(
+            &env,
+            &creator,
+            "Batch",
+            500,
+            &token_client.address,
+            &obligations,
+        )));
+    }
+
+    // One over-deposit and one duplicate reject the whole batch.
+    let bad = Vec::from_array(&env, [(ids[0], 500i128), (ids[1], 600), (ids[0], 1)]);
+    assert_eq!(
+        client.deposit_many(&participant, &bad),
+        Vec::from_array(
+            &env,
+            [
+                BatchOutcome::NotApplied,
+                BatchOutcome::Rejected(crate::Error::InvalidAmount as u32),
+                BatchOutcome::Rejected(crate::Error::InvalidInput as u32),
+            ]
+        )
+    );
+    assert_eq!(client.get_escrow(&ids[0]).deposited_amount, 0);
+    assert_eq!(token_client.balance(&participant), 1_000_000);
+
+    let good = Vec::from_array(&env, [(ids[0], 500i128), (ids[1], 200)]);
+    assert_eq!(
+        client.deposit_many(&participant, &good),
+        Vec::from_array(
+            &env,
+            [BatchOutcome::Applied(500), BatchOutcome::Applied(200)]
+        )
+    );
+    assert_eq!(client.get_escrow(&ids[0]).status, SplitStatus::Ready);
+    assert_eq!(client.get_escrow(&ids[1]).deposited_amount, 200);
+    assert_eq!(token_client.balance(&participant), 1_000_000 - 700);
+
+    assert_eq!(
+        client.try_deposit_many(&participant, &Vec::new(&env)),
+        Err(Ok(crate::Error::InvalidInput))
+    );
+}
+
+#[test]
+fn test_release_many_releases_every_ready_split() {
+    use crate::BatchOutcome;
+
+    let (env, client, admin, creator, participant, token_client, _) = setup();
+    client.set_treasury(&admin, &Address::generate(&env));
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 300i128);
+    let mut ids = Vec::new(&env);
+    for _ in 0..3 {
+        let split_id = client.create_escrow(&escrow_params(
+            &env,
+            &creator,
+            "Close",
+            300,
+            &token_client.address,
+            &obligations,
+        ));
+        ids.push_back(split_id);
+    }
+    client.deposit(&ids.get(0).unwrap(), &participant, &300);
+    client.deposit(&ids.get(1).unwrap(), &participant, &300);
+
+    assert_eq!(
+        client.release_many(&creator, &ids),
+        Vec::from_array(
+            &env,
+            [
+                BatchOutcome::NotApplied,
+                BatchOutcome::NotApplied,
+                BatchOutcome::Rejected(crate::Error::SplitNotReady as u32),
+            ]
+        )
+    );
+    assert_eq!(token_client.balance(&creator), 1_000_000);
+
+    ids.pop_back();
+    assert_eq!(
+        client.release_many(&creator, &ids),
+        Vec::from_array(
+            &env,
+            [BatchOutcome::Applied(300), BatchOutcome::Applied(300)]
+        )
+    );
+    assert_eq!(token_client.balance(&creator), 1_000_000 + 600);
+    for split_id in ids.iter() {
+        assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Released);
+    }
+}
+
+/// Stand-in for the path-payment contract: converts at a fixed rate (output per 1e7 input)
+/// and forwards the output from its own float to `recipient`.
+#[soroban_sdk::contract]
+struct MockPathPayment;
+
+#[soroban_sdk::contractimpl]
+impl MockPathPayment {
+    pub fn set_rate(env: Env, rate: i128) {
+        env.storage().instance().set(&0u32, &rate);
+    }
+
+    pub fn execute_path_payment_to(
+        env: Env,
+        caller: Address,
+        recipient: Address,
+        _split_id: String,
+        path: Vec<crate::PathAsset>,
+        amount_in: i128,
+        _max_slippage: u32,
+    ) -> i128 {
+        caller.require_auth();
+        let rate: i128 = env.storage().instance().get(&0u32).unwrap();
+        let source = path.get(0).unwrap().0;
+        let dest = path.get(path.len() - 1).unwrap().0;
+        TokenClient::new(&env, &source).transfer(
+            &caller,
+            &env.current_contract_address(),
+            &amount_in,
+        );
+        let amount_out = amount_in * rate / 10_000_000;
+        TokenClient::new(&env, &dest).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &amount_out,
+        );
+        amount_out
+    }
+}
+
+#[test]
+fn test_deposit_with_conversion_credits_up_to_obligation_and_refunds_excess() {
+    let (env, client, _admin, creator, participant, token_client, token_admin_client) = setup();
+    let source_admin = Address::generate(&env);
+    let source = env
+        .register_stellar_asset_contract_v2(source_admin)
+        .address();
+    let source_client = TokenClient::new(&env, &source);
+    TokenAdminClient::new(&env, &source).mint(&participant, &1_000);
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 500i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Conversion",
+        500,
+        &token_client.address,
+        &obligations,
+    ));
+    client.deposit(&split_id, &participant, &200);
+
+    // Nothing registered yet.
+    assert_eq!(
+        client.try_deposit_with_conversion(
+            &participant,
+            &split_id,
+            &source,
+            &400,
+            &Vec::new(&env),
+            &100
+        ),
+        Err(Ok(crate::Error::ConversionUnavailable))
+    );
+
+    // The escrow authorizes the path-payment contract's pull of the source asset itself.
+    env.mock_all_auths_allowing_non_root_auth();
+    let path_payment = env.register_contract(None, MockPathPayment);
+    MockPathPaymentClient::new(&env, &path_payment).set_rate(&20_000_000);
+    token_admin_client.mint(&path_payment, &10_000);
+    client.set_path_payment_contract(&path_payment);
+    assert_eq!(
+        client.get_path_payment_contract(),
+        Some(path_payment.clone())
+    );
+
+    // The split token itself is deposited directly, not converted.
+    assert_eq!(
+        client.try_deposit_with_conversion(
+            &participant,
+            &split_id,
+            &token_client.address,
+            &100,
+            &Vec::new(&env),
+            &100
+        ),
+        Err(Ok(crate::Error::InvalidInput))
+    );
+    assert_eq!(token_client.balance(&participant), 1_000_000 - 200);
+
+    // 400 source converts to 800; 300 is outstanding, so 500 comes back.
+    let credited = client.deposit_with_conversion(
+        &participant,
+        &split_id,
+        &source,
+        &400,
+        &Vec::new(&env),
+        &100,
+    );
+    assert_eq!(credited, 300);
+    assert_eq!(source_client.balance(&participant), 600);
+    assert_eq!(source_client.balance(&path_payment), 400);
+    assert_eq!(token_client.balance(&client.address), 500);
+    assert_eq!(token_client.balance(&participant), 1_000_000 - 200 + 500);
+
+    let split = client.get_escrow(&split_id);
+    assert_eq!(split.balances.get(participant.clone()), Some(500));
+    assert_eq!(split.status, SplitStatus::Ready);
+
+    // Fully funded: rejected before any conversion happens.
+    assert_eq!(
+        client.try_deposit_with_conversion(
+            &participant,
+            &split_id,
+            &source,
+            &400,
+            &Vec::new(&env),
+            &100
+        ),
+        Err(Ok(crate::Error::SplitNotPending))
+    );
+    assert_eq!(source_client.balance(&participant), 600);
+}
+
+#[soroban_sdk::contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum MockStakingError {
+    CooldownActive = 1,
+    NoRewardsToClaim = 2,
+}
+
+/// Stand-in for the staking contract with the same entry points, a 7 day cooldown
+/// and rewards topped up by the test.
+#[soroban_sdk::contract]
+struct MockStaking;
+
+#[soroban_sdk::contractimpl]
+impl MockStaking {
+    pub fn initialize(env: Env, token: Address) {
+        env.storage().instance().set(&0u32, &token);
+    }
+
+    pub fn add_rewards(env: Env, amount: i128) {
+        let rewards: i128 = env.storage().instance().get(&4u32).unwrap_or(0);
+        env.storage().instance().set(&4u32, &(rewards + amount));
+    }
+
+    pub fn stake(env: Env, staker: Address, amount: i128) {
+        staker.require_auth();
+        let token: Address = env.storage().instance().get(&0u32).unwrap();
+        TokenClient::new(&env, &token).transfer(&staker, &env.current_contract_address(), &amount);
+        let staked: i128 = env.storage().instance().get(&1u32).unwrap_or(0);
+        env.storage().instance().set(&1u32, &(staked + amount));
+    }
+
+    pub fn unstake(env: Env, staker: Address, amount: i128) {
+        staker.require_auth();
+        let staked: i128 = env.storage().instance().get(&1u32).unwrap();
+        let pending: i128 = env.storage().instance().get(&2u32).unwrap_or(0);
+        env.storage().instance().set(&1u32, &(staked - amount));
+        env.storage().instance().set(&2u32, &(pending + amount));
+        env.storage()
+            .instance()
+            .set(&3u32, &(env.ledger().timestamp() + 604_800));
+    }
+
+    pub fn withdraw(env: Env, staker: Address) -> Result<(), MockStakingError> {
+        staker.require_auth();
+        let unlock: u64 = env.storage().instance().get(&3u32).unwrap_or(0);
+        if env.ledger().timestamp() < unlock {
+            return Err(MockStakingError::CooldownActive);
+        }
+        let token: Address = env.storage().instance().get(&0u32).unwrap();
+        let pending: i128 = env.storage().instance().get(&2u32).unwrap_or(0);
+        env.storage().instance().set(&2u32, &0i128);
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &staker, &pending);
+        Ok(())
+    }
+
+    pub fn claim_staking_rewards(env: Env, staker: Address) -> Result<i128, MockStakingError> {
+        staker.require_auth();
+        let rewards: i128 = env.storage().instance().get(&4u32).unwrap_or(0);
+        if rewards == 0 {
+            return Err(MockStakingError::NoRewardsToClaim);
+        }
+        env.storage().instance().set(&4u32, &0i128);
+        let token: Address = env.storage().instance().get(&0u32).unwrap();
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &staker, &rewards);
+        Ok(rewards)
+    }
+}
+
+#[test]
+fn test_yield_mode_stakes_pays_depositors_and_releases_after_cooldown() {
+    let (env, client, admin, creator, participant, token_client, token_admin_client) = setup();
+    env.mock_all_auths_allowing_non_root_auth();
+    client.set_treasury(&admin, &Address::generate(&env));
+    client.set_fee(&admin, &0u32);
+    let other = Address::generate(&env);
+    token_admin_client.mint(&other, &1_000_000);
+
+    let staking = env.register_contract(None, MockStaking);
+    let staking_client = MockStakingClient::new(&env, &staking);
+    staking_client.initialize(&token_client.address);
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 300i128);
+    obligations.set(other.clone(), 100i128);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Yield",
+        400,
+        &token_client.address,
+        &obligations,
+    );
+    params.yield_policy = YieldPolicy::Depositors;
+    // No staking contract registered yet.
+    assert_eq!(
+        client.try_create_escrow(&params),
+        Err(Ok(crate::Error::StakingUnavailable))
+    );
+    client.set_staking_contract(&staking, &token_client.address);
+    let split_id = client.create_escrow(&params);
+    client.deposit(&split_id, &participant, &300);
+    client.deposit(&split_id, &other, &100);
+
+    assert_eq!(client.stake_idle(&creator, &split_id), 400);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(
+        client.try_stake_idle(&creator, &split_id),
+        Err(Ok(crate::Error::InvalidAmount))
+    );
+
+    // Rewards go pro rata to depositors.
+    token_admin_client.mint(&staking, &40);
+    staking_client.add_rewards(&40);
+    assert_eq!(client.claim_staking_rewards(&split_id), 40);
+    assert_eq!(token_client.balance(&participant), 1_000_000 - 300 + 30);
+    assert_eq!(token_client.balance(&other), 1_000_000 - 100 + 10);
+
+    // Release starts the cooldown instead of paying out.
+    token_admin_client.mint(&staking, &20);
+    staking_client.add_rewards(&20);
+    client.release_funds(&creator, &split_id);
+    assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Unstaking);
+    assert_eq!(token_client.balance(&creator), 1_000_000);
+    assert!(!client.complete_unstake(&split_id));
+
+    env.ledger().with_mut(|l| l.timestamp += 604_800);
+    assert!(client.complete_unstake(&split_id));
+    assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 1_000_000 + 400);
+    assert_eq!(
+        token_client.balance(&participant),
+        1_000_000 - 300 + 30 + 15
+    );
+    assert_eq!(token_client.balance(&other), 1_000_000 - 100 + 10 + 5);
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    let position = client.get_yield_position(&split_id).unwrap();
+    assert_eq!(
+        (position.staked, position.unstaking, position.accrued),
+        (0, 0, 0)
+    );
+}
+
+#[test]
+fn test_cancelled_and_expired_yield_splits_refund_after_cooldown() {
+    let (env, client, admin, creator, participant, token_client, token_admin_client) = setup();
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().set_timestamp(1_000);
+    let staking = env.register_contract(None, MockStaking);
+    let staking_client = MockStakingClient::new(&env, &staking);
+    staking_client.initialize(&token_client.address);
+    client.set_staking_contract(&staking, &token_client.address);
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 300i128);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Yield",
+        300,
+        &token_client.address,
+        &obligations,
+    );
+    params.yield_policy = YieldPolicy::Depositors;
+    params.deadline = Some(2_000);
+    let cancelled = client.create_escrow(&params);
+    let expired = client.create_escrow(&params);
+    for split_id in [cancelled, expired] {
+        client.deposit(&split_id, &participant, &100);
+        client.stake_idle(&creator, &split_id);
+    }
+    token_admin_client.mint(&staking, &20);
+    staking_client.add_rewards(&20);
+
+    // Depositors can get their money back through a pause, rewards included.
+    client.set_paused(&admin, &true);
+    client.cancel_split(&creator, &cancelled);
+    assert_eq!(client.get_escrow(&cancelled).status, SplitStatus::Unstaking);
+    env.ledger().with_mut(|l| l.timestamp += 604_800);
+    assert_eq!(
+        client.try_claim_staking_rewards(&expired),
+        Err(Ok(crate::Error::Paused))
+    );
+    assert!(client.complete_unstake(&cancelled));
+    client.set_paused(&admin, &false);
+    assert_eq!(client.get_escrow(&cancelled).status, SplitStatus::Cancelled);
+    assert_eq!(token_client.balance(&participant), 1_000_000 - 100 + 10);
+
+    client.expire_split(&expired);
+    assert_eq!(client.get_escrow(&expired).status, SplitStatus::Unstaking);
+    env.ledger().with_mut(|l| l.timestamp += 604_800);
+    assert!(client.complete_unstake(&expired));
+    assert_eq!(client.get_escrow(&expired).status, SplitStatus::Expired);
+    assert_eq!(token_client.balance(&participant), 1_000_000 + 20);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_creator_yield_policy_pays_rewards_to_creator() {
+    let (env, client, admin, creator, participant, token_client, token_admin_client) = setup();
+    env.mock_all_auths_allowing_non_root_auth();
+    client.set_treasury(&admin, &Address::generate(&env));
+    client.set_fee(&admin, &0u32);
+    let staking = env.register_contract(None, MockStaking);
+    let staking_client = MockStakingClient::new(&env, &staking);
+    staking_client.initialize(&token_client.address);
+    client.set_staking_contract(&staking, &token_client.address);
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 300i128);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Yield",
+        300,
+        &token_client.address,
+        &obligations,
+    );
+    params.yield_policy = YieldPolicy::Creator;
+    let split_id = client.create_escrow(&params);
+    client.deposit(&split_id, &participant, &300);
+    client.stake_idle(&creator, &split_id);
+
+    token_admin_client.mint(&staking, &30);
+    staking_client.add_rewards(&30);
+    assert_eq!(client.claim_staking_rewards(&split_id), 30);
+    assert_eq!(token_client.balance(&creator), 1_000_000 + 30);
+    assert_eq!(token_client.balance(&participant), 1_000_000 - 300);
+
+    token_admin_client.mint(&staking, &6);
+    staking_client.add_rewards(&6);
+    client.release_funds(&creator, &split_id);
+    env.ledger().with_mut(|l| l.timestamp += 604_800);
+    // Unlike a refund, the release waits out a freeze.
+    client.set_split_frozen(&admin, &split_id, &true);
+    assert_eq!(
+        client.try_complete_unstake(&split_id),
+        Err(Ok(crate::Error::Paused))
+    );
+    client.set_split_frozen(&admin, &split_id, &false);
+    assert!(client.complete_unstake(&split_id));
+    assert_eq!(token_client.balance(&creator), 1_000_000 + 30 + 6 + 300);
+    assert_eq!(token_client.balance(&participant), 1_000_000 - 300);
+}
+
+#[test]
+fn test_stake_idle_waits_for_pending_amendment() {
+    let (env, client, _admin, creator, participant, token_client, _) = setup();
+    env.mock_all_auths_allowing_non_root_auth();
+    let staking = env.register_contract(None, MockStaking);
+    MockStakingClient::new(&env, &staking).initialize(&token_client.address);
+    client.set_staking_contract(&staking, &token_client.address);
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 300i128);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Yield",
+        300,
+        &token_client.address,
+        &obligations,
+    );
+    params.yield_policy = YieldPolicy::Depositors;
+    let split_id = client.create_escrow(&params);
+    client.deposit(&split_id, &participant, &200);
+
+    let mut amended = Map::new(&env);
+    amended.set(participant.clone(), 100i128);
+    client.propose_amendment(&split_id, &amended, &100);
+    // Staking now would leave nothing in the escrow to refund the excess from.
+    assert_eq!(
+        client.try_stake_idle(&creator, &split_id),
+        Err(Ok(crate::Error::AmendmentPending))
+    );
+
+    assert!(client.approve_amendment(&split_id, &participant));
+    assert_eq!(token_client.balance(&participant), 1_000_000 - 100);
+    assert_eq!(client.stake_idle(&creator, &split_id), 100);
+}
+
+#[test]
+fn test_later_exit_queues_instead_of_restarting_cooldown() {
+    let (env, client, admin, creator, participant, token_client, _) = setup();
+    env.mock_all_auths_allowing_non_root_auth();
+    client.set_treasury(&admin, &Address::generate(&env));
+    client.set_fee(&admin, &0u32);
+    let staking = env.register_contract(None, MockStaking);
+    MockStakingClient::new(&env, &staking).initialize(&token_client.address);
+    client.set_staking_contract(&staking, &token_client.address);
+
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 100i128);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Yield",
+        100,
+        &token_client.address,
+        &obligations,
+    );
+    params.yield_policy = YieldPolicy::Depositors;
+    let first = client.create_escrow(&params);
+    let second = client.create_escrow(&params);
+    for split_id in [first, second] {
+        client.deposit(&split_id, &participant, &100);
+        client.stake_idle(&creator, &split_id);
+    }
+
+    client.release_funds(&creator, &first);
+    env.ledger().with_mut(|l| l.timestamp += 3 * 86_400);
+    // The first split's batch is cooling down, so the second exit waits behind it.
+    client.release_funds(&creator, &second);
+
+    env.ledger().with_mut(|l| l.timestamp += 4 * 86_400);
+    assert!(client.complete_unstake(&first));
+    assert_eq!(client.get_escrow(&first).status, SplitStatus::Released);
+    // Withdrawing the first batch started the second one's cooldown.
+    assert!(!client.complete_unstake(&second));
+    assert_eq!(client.get_escrow(&second).status, SplitStatus::Unstaking);
+
+    env.ledger().with_mut(|l| l.timestamp += 604_800);
+    assert!(client.complete_unstake(&second));
+    assert_eq!(client.get_escrow(&second).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 1_000_000 + 200);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_transfer_obligation_moves_share_and_excess_balance() {
+    let (env, client, _admin, creator, participant, token_client, _) = setup();
+    let roommate = Address::generate(&env);
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 300i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Rent",
+        300,
+        &token_client.address,
+        &obligations,
+    ));
+    client.deposit(&split_id, &participant, &200);
+
+    // 100 of the 300 is still unpaid, so handing over 150 moves 50 of the deposit too.
+    let events_before = env.events().all().len();
+    client.transfer_obligation(&split_id, &participant, &roommate, &150);
+    assert_eq!(env.events().all().len(), events_before + 1);
+
+    let split = client.get_escrow(&split_id);
+    assert_eq!(split.obligations.get(participant.clone()), Some(150));
+    assert_eq!(split.obligations.get(roommate.clone()), Some(150));
+    assert_eq!(split.balances.get(participant.clone()), Some(150));
+    assert_eq!(split.balances.get(roommate.clone()), Some(50));
+    assert_eq!(split.deposited_amount, 200);
+    assert_eq!(split.participants.len(), 2);
+    assert_eq!(
+        client
+            .get_splits_by_participant(&roommate, &None, &0, &10)
+            .split_ids,
+        Vec::from_array(&env, [split_id])
+    );
+
+    // Handing over the rest moves the whole balance and drops the participant.
+    client.transfer_obligation(&split_id, &participant, &roommate, &150);
+    let split = client.get_escrow(&split_id);
+    assert_eq!(split.obligations.get(participant.clone()), None);
+    assert_eq!(split.obligations.get(roommate.clone()), Some(300));
+    assert_eq!(split.balances.get(roommate.clone()), Some(200));
+    assert_eq!(
+        split.participants,
+        Vec::from_array(&env, [roommate.clone()])
+    );
+    assert_eq!(
+        client
+            .get_splits_by_participant(&participant, &None, &0, &10)
+            .split_ids,
+        Vec::new(&env)
+    );
+
+    assert_eq!(
+        client.try_transfer_obligation(&split_id, &roommate, &participant, &301),
+        Err(Ok(crate::Error::InvalidAmount))
+    );
+    client.toggle_whitelist(&creator, &split_id, &true);
+    client.add_to_whitelist(&creator, &split_id, &roommate);
+    assert_eq!(
+        client.try_transfer_obligation(&split_id, &roommate, &participant, &100),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_transfer_obligation_respects_participant_cap() {
+    let (env, client, _admin, creator, participant, token_client, token_admin_client) = setup();
+    let other = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+    token_admin_client.mint(&other, &1_000);
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 100i128);
+    obligations.set(other.clone(), 100i128);
+    let mut params = escrow_params(
+        &env,
+        &creator,
+        "Capped",
+        200,
+        &token_client.address,
+        &obligations,
+    );
+    params.max_participants = Some(2);
+    let split_id = client.create_escrow(&params);
+    client.deposit(&split_id, &participant, &100);
+    client.deposit(&split_id, &other, &50);
+
+    // `participant` would stay a depositor and `newcomer` would be a third.
+    assert_eq!(
+        client.try_transfer_obligation(&split_id, &participant, &newcomer, &50),
+        Err(Ok(crate::Error::ParticipantCapExceeded))
+    );
+    // Handing over everything frees the slot.
+    client.transfer_obligation(&split_id, &participant, &newcomer, &100);
+    let split = client.get_escrow(&split_id);
+    assert_eq!(split.participants.len(), 2);
+    assert_eq!(split.balances.get(newcomer.clone()), Some(100));
+}
+
+#[test]
+fn test_co_manager_acts_only_within_its_permissions() {
+    let (env, client, admin, creator, participant, token_client, _) = setup();
+    client.set_treasury(&admin, &Address::generate(&env));
+    let manager = Address::generate(&env);
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 500i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Managed",
+        500,
+        &token_client.address,
+        &obligations,
+    ));
+    client.deposit(&split_id, &participant, &500);
+
+    assert_eq!(
+        client.try_set_manager(
+            &split_id,
+            &creator,
+            &Vec::from_array(&env, [Permission::Release])
+        ),
+        Err(Ok(crate::Error::InvalidInput))
+    );
+    client.set_manager(
+        &split_id,
+        &manager,
+        &Vec::from_array(&env, [Permission::EditNote, Permission::Release]),
+    );
+    assert_eq!(client.get_managers(&split_id).len(), 1);
+    assert!(client.has_permission(&split_id, &manager, &Permission::Release));
+    assert!(!client.has_permission(&split_id, &manager, &Permission::Cancel));
+    assert!(client.has_permission(&split_id, &creator, &Permission::Cancel));
+
+    client.set_note(&manager, &split_id, &String::from_str(&env, "from manager"));
+    assert_eq!(
+        client.try_cancel_split(&manager, &split_id),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_toggle_whitelist(&manager, &split_id, &true),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_release_funds(&participant, &split_id),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+
+    // Revoking leaves the manager with nothing.
+    client.set_manager(&split_id, &manager, &Vec::new(&env));
+    assert!(client.get_managers(&split_id).is_empty());
+    assert_eq!(
+        client.try_release_funds(&manager, &split_id),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+
+    client.set_manager(
+        &split_id,
+        &manager,
+        &Vec::from_array(&env, [Permission::Release]),
+    );
+    client.release_funds(&manager, &split_id);
+    assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Released);
+    // Funds still go to the creator, not the manager who released them.
+    assert_eq!(token_client.balance(&creator), 1_000_000 + 500);
+    assert_eq!(token_client.balance(&manager), 0);
+}
+
+#[test]
+fn test_co_manager_releases_many_where_permitted() {
+    use crate::BatchOutcome;
+
+    let (env, client, admin, creator, participant, token_client, _) = setup();
+    client.set_treasury(&admin, &Address::generate(&env));
+    let manager = Address::generate(&env);
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 300i128);
+    let mut ids = Vec::new(&env);
+    for _ in 0..2 {
+        let split_id = client.create_escrow(&escrow_params(
+            &env,
+            &creator,
+            "Managed",
+            300,
+            &token_client.address,
+            &obligations,
+        ));
+        client.deposit(&split_id, &participant, &300);
+        ids.push_back(split_id);
+    }
+    let release = Vec::from_array(&env, [Permission::Release]);
+    client.set_manager(&ids.get(0).unwrap(), &manager, &release);
+
+    assert_eq!(
+        client.release_many(&manager, &ids),
+        Vec::from_array(
+            &env,
+            [
+                BatchOutcome::NotApplied,
+                BatchOutcome::Rejected(crate::Error::Unauthorized as u32),
+            ]
+        )
+    );
+
+    client.set_manager(&ids.get(1).unwrap(), &manager, &release);
+    assert_eq!(
+        client.release_many(&manager, &ids),
+        Vec::from_array(
+            &env,
+            [BatchOutcome::Applied(300), BatchOutcome::Applied(300)]
+        )
+    );
+    assert_eq!(token_client.balance(&creator), 1_000_000 + 600);
+    assert_eq!(token_client.balance(&manager), 0);
+}
+
+#[test]
+fn test_creator_handover_takes_two_steps() {
+    let (env, client, admin, creator, participant, token_client, _) = setup();
+    let successor = Address::generate(&env);
+    let mut obligations = Map::new(&env);
+    obligations.set(participant.clone(), 500i128);
+    let split_id = client.create_escrow(&escrow_params(
+        &env,
+        &creator,
+        "Handover",
+        500,
+        &token_client.address,
+        &obligations,
+    ));
+    client.deposit(&split_id, &participant, &200);
+
+    assert_eq!(
+        client.try_accept_creator(&split_id),
+        Err(Ok(crate::Error::NoPendingCreator))
+    );
+    client.set_manager(
+        &split_id,
+        &successor,
+        &Vec::from_array(&env, [Permission::EditNote]),
+    );
+    client.propose_creator(&split_id, &successor);
+    assert_eq!(
+        client.get_pending_creator(&split_id),
+        Some(successor.clone())
+    );
+    // Nothing changes until the nominee accepts.
+    assert_eq!(client.get_creator(&split_id), creator);
+
+    // A frozen split's control stays put.
+    client.set_split_frozen(&admin, &split_id, &true);
+    assert_eq!(
+        client.try_accept_creator(&split_id),
+        Err(Ok(crate::Error::Paused))
+    );
+    assert_eq!(
+        client.try_propose_creator(&split_id, &participant),
+        Err(Ok(crate::Error::Paused))
+    );
+    assert_eq!(
+        client.try_set_manager(&split_id, &participant, &Vec::new(&env)),
+        Err(Ok(crate::Error::Paused))
+    );
+    client.set_split_frozen(&admin, &split_id, &false);
+
+    client.accept_creator(&split_id);
+    assert_eq!(client.get_creator(&split_id), successor);
+    assert_eq!(client.get_pending_creator(&split_id), None);
+    assert!(client.get_managers(&split_id).is_empty());
+    assert_eq!(
+        client
+            .get_splits_by_creator(&creator, &None, &0, &10)
+            .split_ids,
+        Vec::new(&env)
+    );
+    assert_eq!(
+        client
+            .get_splits_by_creator(&successor, &None, &0, &10)
+            .split_ids,
+        Vec::from_array(&env, [split_id])
+    );
+
+    assert_eq!(
+        client.try_cancel_split(&creator, &split_id),
+        Err(Ok(crate::Error::Unauthorized))
+    );
+    client.cancel_split(&successor, &split_id);
+    assert_eq!(client.get_escrow(&split_id).status, SplitStatus::Cancelled);
+    assert_eq!(token_client.balance(&participant), 1_000_000);
+}
+
+// ============================================================
+// Property / invariant tests (proptest-style state transitions)
+// ============================================================
+
+/// Random sequences of escrow operations against a real Stellar asset contract, with
+/// global invariants checked after every step. Failing sequences shrink to a minimal
+/// list of `Op`s, which proptest records under `proptest-regressions/` for replay.
+mod proptests {
+    use super::*;
+    use crate::fees::MAX_BPS;
+    use crate::storage;
+    use proptest::prelude::*;
+
+    const PARTICIPANTS: usize = 3;
+
+    #[derive(Clone, Debug)]
+    enum Op {
+        /// Obligation per participant; zero means not owed.
+        Create([i128; PARTICIPANTS]),
+        Deposit {
+            split: usize,
+            participant: usize,
+            amount: i128,
+        },
+        /// Every participant deposits what they still owe, so releases are reachable.
+        Fund {
+            split: usize,
+        },
+        Cancel {
+            split: usize,
+        },
+        Release {
+            split: usize,
+        },
+        SetFee(u32),
+        ToggleWhitelist {
+            split: usize,
+            enabled: bool,
+        },
+        Whitelist {
+            split: usize,
+            participant: usize,
+            add: bool,
+        },
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            prop::array::uniform3(0i128..=400).prop_map(Op::Create),
+            (0usize..4, 0usize..PARTICIPANTS, 1i128..=500).prop_map(
+                |(split, participant, amount)| Op::Deposit {
+                    split,
+                    participant,
+                    amount
+                }
+            ),
+            (0usize..4).prop_map(|split| Op::Fund { split }),
+            (0usize..4).prop_map(|split| Op::Cancel { split }),
+            (0usize..4).prop_map(|split| Op::Release { split }),
+            (0u32..=MAX_BPS + 2_000).prop_map(Op::SetFee),
+            (0usize..4, any::<bool>())
+                .prop_map(|(split, enabled)| Op::ToggleWhitelist { split, enabled }),
+            (0usize..4, 0usize..PARTICIPANTS, any::<bool>()).prop_map(
+                |(split, participant, add)| Op::Whitelist {
+                    split,
+                    participant,
+                    add
+                }
+            ),
+        ]
+    }
+
+    fn is_terminal(status: &SplitStatus) -> bool {
+        matches!(
+            status,
+            SplitStatus::Released | SplitStatus::Cancelled | SplitStatus::Expired
+        )
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig { cases: 32, .. ProptestConfig::default() })]
+        #[test]
+        fn prop_escrow_invariants_hold_for_any_sequence(
+            ops in prop::collection::vec(op(), 1usize..=30)
+        ) {
+            let (env, client, admin, creator, _, token_client, token_admin_client) = setup();
+            let treasury = Address::generate(&env);
+            client.set_treasury(&admin, &treasury);
+            let participants: std::vec::Vec<Address> =
+                (0..PARTICIPANTS).map(|_| Address::generate(&env)).collect();
+            for p in &participants {
+                token_admin_client.mint(p, &1_000_000);
+            }
+
+            let mut splits: std::vec::Vec<u64> = std::vec::Vec::new();
+            // Each split as it was when it first reached a terminal state.
+            let mut finished: std::collections::BTreeMap<u64, crate::Split> =
+                std::collections::BTreeMap::new();
+
+            for op in ops {
+                let pick = |i: usize| splits.get(i % splits.len().max(1)).copied();
+                match op {
+                    Op::Create(amounts) => {
+                        let mut obligations = Map::new(&env);
+                        for (p, amount) in participants.iter().zip(amounts) {
+                            if amount > 0 {
+                                obligations.set(p.clone(), amount);
+                            }
+                        }
+                        let total = amounts.iter().sum::<i128>();
+                        let params = escrow_params(
+                            &env,
+                            &creator,
+                            "Prop",
+                            total,
+                            &token_client.address,
+                            &obligations,
+                        );
+                        if let Ok(Ok(split_id)) = client.try_create_escrow(&params) {
+                            splits.push(split_id);
+                        }
+                    }
+                    Op::Deposit { split, participant, amount } => {
+                        if let Some(split_id) = pick(split) {
+                            let _ = client.try_deposit(&split_id, &participants[participant], &amount);
+                        }
+                    }
+                    Op::Fund { split } => {
+                        if let Some(split_id) = pick(split) {
+                            let escrow = client.get_escrow(&split_id);
+                            for (participant, obligation) in escrow.obligations.iter() {
+                                let owed = obligation - escrow.balances.get(participant.clone()).unwrap_or(0);
+                                let _ = client.try_deposit(&split_id, &participant, &owed);
+                            }
+                        }
+                    }
+                    Op::Cancel { split } => {
+                        if let Some(split_id) = pick(split) {
+                            let _ = client.try_cancel_split(&creator, &split_id);
+                        }
+                    }
+                    Op::Release { split } => {
+                        if let Some(split_id) = pick(split) {
+                            let before = client.get_escrow(&split_id);
+                            let treasury_before = token_client.balance(&treasury);
+                            let fee_bps = env.as_contract(&client.address, || storage::get_fee_bps(&env));
+                            if client.try_release_funds(&creator, &split_id).is_ok() {
+                                let gross = before.deposited_amount - before.released_amount;
+                                let fee = token_client.balance(&treasury) - treasury_before;
+                                prop_assert!(fee >= 0 && fee <= gross * fee_bps as i128 / MAX_BPS as i128);
+                            }
+                        }
+                    }
+                    Op::SetFee(bps) => {
+                        let _ = client.try_set_fee(&admin, &bps);
+                    }
+                    Op::ToggleWhitelist { split, enabled } => {
+                        if let Some(split_id) = pick(split) {
+                            let _ = client.try_toggle_whitelist(&creator, &split_id, &enabled);
+                        }
+                    }
+                    Op::Whitelist { split, participant, add } => {
+                        if let Some(split_id) = pick(split) {
+                            let address = &participants[participant];
+                            let _ = if add {
+                                client.try_add_to_whitelist(&creator, &split_id, address)
+                            } else {
+                                client.try_remove_from_whitelist(&creator, &split_id, address)
+                            };
+                        }
+                    }
+                }
+
+                // Fees never exceed MAX_BPS.
+                let fee_bps = env.as_contract(&client.address, || storage::get_fee_bps(&env));
+                prop_assert!(fee_bps <= MAX_BPS);
+
+                let mut held = 0i128;
+                for split_id in &splits {
+                    let split = client.get_escrow(split_id);
+                    // No balance exceeds its obligation.
+                    for (participant, balance) in split.balances.iter() {
+                        let obligation = split.obligations.get(participant).unwrap_or(0);
+                        prop_assert!(balance <= obligation);
+                    }
+                    // Terminal states never change.
+                    match finished.get(split_id) {
+                        Some(terminal) => {
+                            prop_assert_eq!(&split.status, &terminal.status);
+                            prop_assert_eq!(split.deposited_amount, terminal.deposited_amount);
+                            prop_assert_eq!(split.released_amount, terminal.released_amount);
+                        }
+                        None if is_terminal(&split.status) => {
+                            finished.insert(*split_id, split.clone());
+                        }
+                        None => held += split.deposited_amount - split.released_amount,
+                    }
+                }
+                // The escrow holds exactly what live splits have on deposit.
+                prop_assert_eq!(token_client.balance(&client.address), held);
+            }
+        }
+    }
+}
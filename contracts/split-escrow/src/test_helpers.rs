@@ -19,6 +19,7 @@ pub fn make_split(
     env: &Env,
     split_id: u64,
     creator: Address,
+    token: Address,
     obligations: Map<Address, i128>,
 ) -> Split {
     let total_amount: i128 = {
@@ -41,6 +42,7 @@ pub fn make_split(
         split_id,
         creator,
         description: String::from_str(env, "test escrow"),
+        token,
         metadata: Map::new(env),
         total_amount,
         deposited_amount: 0,
@@ -50,6 +52,11 @@ pub fn make_split(
         balances: Map::new(env),
         obligations,
         note: String::from_str(env, ""),
+        deadline: None,
+        payouts: soroban_sdk::Vec::new(env),
+        milestones: soroban_sdk::Vec::new(env),
+        milestones_released: 0,
+        released_amount: 0,
     }
 }
 
@@ -1,13 +1,91 @@
-use soroban_sdk::{contracttype, Address, Map, String, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, Map, String, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum SplitStatus {
     Pending,
     Ready,
+    /// Some, but not all, milestone tranches have been paid out.
+    PartiallyReleased,
     /// Funds refunded to participants (e.g. dispute upheld).
     Cancelled,
     Released,
+    /// Deadline passed before release; deposits were refunded to participants.
+    Expired,
+    /// On hold while the registered dispute contract decides between release and refund.
+    Disputed,
+    /// Staked funds are in the staking contract's cooldown; `complete_unstake` then
+    /// finishes the release, cancellation or expiry that started it.
+    Unstaking,
+}
+
+/// Split actions the creator can delegate to co-managers. The creator holds them all.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Permission {
+    /// `release_funds` and `release_milestone`, and resolving a dispute on the split.
+    Release,
+    Cancel,
+    EditNote,
+    ManageWhitelist,
+}
+
+/// Delegated permissions. The admin implicitly holds every role.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// May change the fee rate, fee schedule, creator overrides and treasury.
+    FeeManager,
+    /// May pause the contract or freeze individual splits.
+    Pauser,
+    /// May pull pre-approved deposits with `collect_from` on any split.
+    Keeper,
+}
+
+/// One volume bracket: splits with `deposited_amount >= min_amount` pay `fee_bps`
+/// unless a higher bracket also matches.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeTier {
+    pub min_amount: i128,
+    pub fee_bps: u32,
+}
+
+/// Admin-configured fee schedule. With no tiers, the flat `set_fee` rate applies.
+/// The resulting fee is clamped to `[min_fee, max_fee]`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSchedule {
+    /// Brackets ordered by strictly increasing `min_amount`, the first starting at 0.
+    pub tiers: Vec<FeeTier>,
+    pub min_fee: i128,
+    pub max_fee: Option<i128>,
+}
+
+/// Which rule priced a release; reported in the `FeesCollected` event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AppliedFee {
+    Flat,
+    Tier(u32),
+    CreatorOverride,
+}
+
+/// How much of the released amount a payout recipient receives.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayoutShare {
+    /// Weight in basis points; a table of these must sum to 10_000.
+    Bps(u32),
+    /// Amount out of `total_amount`; a table of these must sum to `total_amount`.
+    Fixed(i128),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payout {
+    pub recipient: Address,
+    pub share: PayoutShare,
 }
 
 /// Escrow split state. `participants.len()` is the current distinct participant count.
@@ -17,11 +95,14 @@ pub struct Split {
     pub split_id: u64,
     pub creator: Address,
     pub description: String,
+    /// Asset escrowed by this split; must be on the admin allowlist at creation.
+    pub token: Address,
     pub metadata: Map<String, String>,
     pub total_amount: i128,
     pub deposited_amount: i128,
     pub status: SplitStatus,
-    /// Maximum distinct participants allowed (default 50 at creation if not specified).
+    /// Maximum distinct participants allowed (`Limits::max_participants` at
+    /// creation if not specified).
     pub max_participants: u32,
     /// Distinct addresses that have deposited; length is the current participant count.
     pub participants: Vec<Address>,
@@ -31,4 +112,263 @@ pub struct Split {
     pub obligations: Map<Address, i128>,
     /// Short on-chain context (max 128 bytes at creation/update); empty if unset.
     pub note: String,
+    /// Ledger timestamp after which a `Pending` split may be expired and refunded.
+    pub deadline: Option<u64>,
+    /// Release distribution; empty means the creator receives everything net of fees.
+    pub payouts: Vec<Payout>,
+    /// Ordered tranche amounts summing to `total_amount`; empty means a single release.
+    pub milestones: Vec<i128>,
+    /// Number of leading `milestones` already paid out.
+    pub milestones_released: u32,
+    /// Gross amount (before fees) already paid out of the escrow.
+    pub released_amount: i128,
+}
+
+/// A schedule that instantiates one `Split` per period. Each generated split pays
+/// the creator and uses the default participant cap.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RecurringSplit {
+    pub recurring_id: u64,
+    pub creator: Address,
+    pub description: String,
+    pub token: Address,
+    /// Total of every cycle; equals the sum of `obligations`.
+    pub total_amount: i128,
+    /// Per-participant contribution owed each cycle.
+    pub obligations: Map<Address, i128>,
+    /// Seconds between cycle starts.
+    pub period: u64,
+    /// Number of cycles the schedule produces in total.
+    pub cycles: u32,
+    /// Ledger timestamp from which the next cycle may be rolled.
+    pub next_cycle_at: u64,
+    pub paused: bool,
+    /// Split ids created so far, oldest first.
+    pub history: Vec<u64>,
+}
+
+/// Position of a split inside a recurring schedule.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CycleLink {
+    pub recurring_id: u64,
+    /// Zero-based cycle number.
+    pub cycle: u32,
+    /// Split id of the preceding cycle, if any.
+    pub previous: Option<u64>,
+}
+
+/// Proposed replacement for a split's obligations, applied once every affected
+/// participant has approved.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Amendment {
+    pub obligations: Map<Address, i128>,
+    /// New `total_amount`; equals the sum of `obligations`.
+    pub total_amount: i128,
+    /// Participants whose obligation changes, including ones added or removed.
+    pub affected: Vec<Address>,
+    /// Subset of `affected` that has approved so far.
+    pub approvals: Vec<Address>,
+}
+
+/// Deposit authorized off-chain by a participant and submitted by a relayer.
+/// The participant signs an authorization for exactly this value.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DepositIntent {
+    pub split_id: u64,
+    /// Gross amount taken from the participant, including `relayer_fee`.
+    pub amount: i128,
+    /// Must equal the participant's next intent nonce.
+    pub nonce: u64,
+    /// Last ledger timestamp at which the intent may be submitted.
+    pub expiry: u64,
+    /// Portion of `amount` paid to `relayer`; zero for none.
+    pub relayer_fee: i128,
+    /// Address paid `relayer_fee`. Signed with the rest of the intent so whoever submits
+    /// it cannot redirect the fee.
+    pub relayer: Address,
+}
+
+/// Admin-configurable size limits applied when splits are created or edited.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Limits {
+    /// Highest participant cap `create_escrow` accepts, and the one it uses when given none.
+    pub max_participants: u32,
+    /// Longest note, in bytes.
+    pub max_note_len: u32,
+    pub max_metadata_entries: u32,
+    /// Longest metadata key or value, in bytes.
+    pub max_metadata_string_len: u32,
+}
+
+/// How far persistent entries and the contract instance are extended, in ledgers.
+/// An entry is extended to `extend_to` whenever it is touched with fewer than
+/// `threshold` ledgers of TTL left.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlPolicy {
+    pub threshold: u32,
+    pub extend_to: u32,
+}
+
+/// Ledger position at which a split entered `status`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusStamp {
+    pub status: SplitStatus,
+    pub ledger: u32,
+    pub timestamp: u64,
+}
+
+/// Settlement facts recorded as a split progresses; the source for receipts.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SettlementLog {
+    /// Total fee taken across every release.
+    pub fee: i128,
+    /// Treasury that received the most recent fee.
+    pub treasury: Option<Address>,
+    /// Net amount paid to each recipient across every release.
+    pub payouts: Map<Address, i128>,
+    /// Each participant's deposited balance when a cancellation or expiry refunded it.
+    pub deposits: Map<Address, i128>,
+    /// Amount each participant got back from that refund.
+    pub refunds: Map<Address, i128>,
+    /// Every status the split has entered, oldest first.
+    pub history: Vec<StatusStamp>,
+}
+
+/// One participant's line on a receipt.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptLine {
+    pub participant: Address,
+    pub obligation: i128,
+    /// Amount the participant deposited, including any later refunded.
+    pub paid: i128,
+    /// Amount returned to the participant when the split was cancelled or expired.
+    pub refunded: i128,
+}
+
+/// Everything a settlement receipt attests to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementSummary {
+    pub split_id: u64,
+    pub token: Address,
+    pub status: SplitStatus,
+    pub total_amount: i128,
+    pub lines: Vec<ReceiptLine>,
+    pub fee: i128,
+    pub treasury: Option<Address>,
+    pub payouts: Map<Address, i128>,
+    pub history: Vec<StatusStamp>,
+}
+
+/// A settlement summary plus `sha256(summary.to_xdr())`, which can be pinned off-chain
+/// and recomputed from the summary alone.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementReceipt {
+    pub summary: SettlementSummary,
+    pub commitment: BytesN<32>,
+}
+
+/// One page of an index query. Pass `next` as the cursor of the following call; it is
+/// `None` once the index is exhausted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitIdPage {
+    pub split_ids: Vec<u64>,
+    pub next: Option<u32>,
+}
+
+/// Per-item result of a batch call. Batches are all or nothing: if any item is
+/// `Rejected`, every other item is `NotApplied` and no state changes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BatchOutcome {
+    /// Item applied; carries the amount deposited or released.
+    Applied(i128),
+    /// Item failed validation; carries the `Error` code.
+    Rejected(u32),
+    /// Item was valid but not applied because another item failed.
+    NotApplied,
+}
+
+/// Asset on a conversion path, identified by its token contract. Mirrors the
+/// path-payment contract's `Asset` so paths can be passed across unchanged.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PathAsset(pub Address);
+
+/// Who receives the staking rewards earned by a yield-mode split.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum YieldPolicy {
+    /// Funds stay idle in the escrow.
+    Disabled,
+    /// Pro rata to deposited balances.
+    Depositors,
+    Creator,
+}
+
+/// Staking contract used for yield mode, and the token it stakes (it exposes no getter).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakingConfig {
+    pub contract: Address,
+    pub token: Address,
+}
+
+/// The escrow's single position in the staking contract, shared by all yield-mode splits.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct YieldPool {
+    /// Sum of `YieldPosition::staked` over all splits.
+    pub total_staked: i128,
+    /// Rewards per staked unit, scaled by 1e12, accumulated since the first stake.
+    pub reward_index: i128,
+    /// Unstaked but still in the staking contract's cooldown.
+    pub pending_withdrawal: i128,
+    /// Withdrawn from the staking contract but not yet claimed by `complete_unstake`.
+    pub withdrawn: i128,
+    /// Exits waiting for the batch in `pending_withdrawal` to be withdrawn before they
+    /// are unstaked as the next batch.
+    pub queued: i128,
+    /// Unstake batches started so far.
+    pub batch: u32,
+}
+
+/// Recorded when a split is put on dispute hold.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeHold {
+    /// Status the split returns to when the hold is lifted.
+    pub previous: SplitStatus,
+    /// Ledger timestamp at which the hold was placed.
+    pub since: u64,
+}
+
+/// A split's share of the `YieldPool`. Present only for splits created in yield mode.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YieldPosition {
+    pub policy: YieldPolicy,
+    pub staked: i128,
+    /// Amount unstaked by the exit in progress.
+    pub unstaking: i128,
+    /// `YieldPool::reward_index` when `accrued` was last brought up to date.
+    pub reward_index: i128,
+    /// Rewards earned but not yet paid out.
+    pub accrued: i128,
+    /// Status `complete_unstake` moves the split to; only meaningful while the split
+    /// is `Unstaking`.
+    pub exit: SplitStatus,
+    /// `YieldPool` batch the exit in progress is unstaked in.
+    pub batch: u32,
 }